@@ -0,0 +1,62 @@
+use anyhow::{Context, Result, bail};
+
+pub const USAGE: &str = "Usage: hyakki [--seed <N>]";
+
+/// Command-line options of the `hyakki` binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Args {
+    /// Start a new run from this seed instead of resuming the saved one
+    pub seed: Option<u64>,
+}
+
+impl Args {
+    /// Parses the arguments that follow the program name
+    ///
+    /// Accepts `--seed <N>` and `--seed=<N>`.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let value = match arg.split_once('=') {
+                Some(("--seed", value)) => value.to_string(),
+                None if arg == "--seed" => args.next().context("--seed needs a value")?,
+                _ => bail!("Unknown argument '{}'\n{}", arg, USAGE),
+            };
+            let seed = value
+                .parse()
+                .with_context(|| format!("Invalid seed '{}': expected a number", value))?;
+            parsed.seed = Some(seed);
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args> {
+        Args::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_no_arguments() {
+        assert_eq!(parse(&[]).unwrap(), Args { seed: None });
+    }
+
+    #[test]
+    fn test_seed_forms() {
+        assert_eq!(parse(&["--seed", "42"]).unwrap().seed, Some(42));
+        assert_eq!(parse(&["--seed=42"]).unwrap().seed, Some(42));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--seed", "abc"]).is_err());
+        assert!(parse(&["--seed=-1"]).is_err());
+        assert!(parse(&["--sed", "42"]).is_err());
+    }
+}
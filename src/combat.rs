@@ -0,0 +1,162 @@
+use crate::ecs::Health;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Hit chance when attack equals defense
+const BASE_HIT_CHANCE: f64 = 0.75;
+/// Hit chance change per point of attack above (or below) the defender's defense
+const HIT_CHANCE_PER_POINT: f64 = 0.05;
+const MIN_HIT_CHANCE: f64 = 0.2;
+const MAX_HIT_CHANCE: f64 = 0.95;
+
+/// Attack and defense, which combat weighs against each other; hit points
+/// are a separate `Health` component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub attack: i32,
+    pub defense: i32,
+}
+
+impl Stats {
+    pub fn new(attack: i32, defense: i32) -> Self {
+        Stats { attack, defense }
+    }
+}
+
+/// Result of a single attack or damage application
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackResult {
+    Miss,
+    Hit { damage: i32 },
+    Kill { damage: i32 },
+}
+
+/// Probability that `attacker` hits `defender`
+///
+/// ```text
+/// attack - defense:  -5    0    +4
+/// hit chance:        0.5  0.75  0.95 (capped)
+/// ```
+pub fn hit_chance(attacker: &Stats, defender: &Stats) -> f64 {
+    let diff = (attacker.attack - defender.defense) as f64;
+    (BASE_HIT_CHANCE + diff * HIT_CHANCE_PER_POINT).clamp(MIN_HIT_CHANCE, MAX_HIT_CHANCE)
+}
+
+/// Rolls a melee attack without applying it
+///
+/// Returns `None` on a miss, otherwise the damage dealt: `attack - defense`
+/// with a random spread of ±1, never less than 1 so that every hit makes progress.
+pub fn roll_melee_damage<R: Rng>(attacker: &Stats, defender: &Stats, rng: &mut R) -> Option<i32> {
+    if !rng.gen_bool(hit_chance(attacker, defender)) {
+        return None;
+    }
+    Some((attacker.attack - defender.defense + rng.gen_range(-1..=1)).max(1))
+}
+
+/// Resolves a melee attack: rolls to hit, then applies damage to the
+/// defender's `health`
+pub fn melee_attack<R: Rng>(
+    attacker: &Stats,
+    defender: &Stats,
+    health: &mut Health,
+    rng: &mut R,
+) -> AttackResult {
+    match roll_melee_damage(attacker, defender, rng) {
+        Some(damage) => apply_damage(health, damage),
+        None => AttackResult::Miss,
+    }
+}
+
+/// Applies `damage` to `health`
+///
+/// This is the common damage pipeline: every source of damage (melee, spells,
+/// traps...) goes through here so that death is detected consistently.
+pub fn apply_damage(health: &mut Health, damage: i32) -> AttackResult {
+    health.hp -= damage;
+    if health.is_dead() {
+        AttackResult::Kill { damage }
+    } else {
+        AttackResult::Hit { damage }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_apply_damage_hit() {
+        let mut health = Health::new(10);
+        assert_eq!(
+            apply_damage(&mut health, 3),
+            AttackResult::Hit { damage: 3 }
+        );
+        assert_eq!(health.hp, 7);
+    }
+
+    #[test]
+    fn test_apply_damage_kill() {
+        let mut health = Health::new(10);
+        assert_eq!(
+            apply_damage(&mut health, 10),
+            AttackResult::Kill { damage: 10 }
+        );
+        assert!(health.is_dead());
+    }
+
+    #[test]
+    fn test_hit_chance_is_clamped() {
+        let weak = Stats::new(0, 0);
+        let strong = Stats::new(100, 100);
+        assert_eq!(hit_chance(&weak, &strong), MIN_HIT_CHANCE);
+        assert_eq!(hit_chance(&strong, &weak), MAX_HIT_CHANCE);
+        assert_eq!(hit_chance(&weak, &weak), BASE_HIT_CHANCE);
+    }
+
+    #[test]
+    fn test_melee_damage_is_at_least_one() {
+        let attacker = Stats::new(1, 0);
+        let defender = Stats::new(0, 10);
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..50 {
+            let mut health = Health::new(100);
+            match melee_attack(&attacker, &defender, &mut health, &mut rng) {
+                AttackResult::Hit { damage } => assert!(damage >= 1),
+                AttackResult::Miss => {}
+                AttackResult::Kill { .. } => panic!("Defender should not die"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_melee_attack_is_deterministic_with_seed() {
+        let attacker = Stats::new(5, 0);
+        let defender = Stats::new(0, 2);
+        let results: Vec<Vec<AttackResult>> = (0..2)
+            .map(|_| {
+                let mut rng = StdRng::seed_from_u64(99);
+                let mut health = Health::new(100);
+                (0..10)
+                    .map(|_| melee_attack(&attacker, &defender, &mut health, &mut rng))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(results[0], results[1]);
+    }
+
+    #[test]
+    fn test_repeated_attacks_eventually_kill() {
+        let attacker = Stats::new(5, 0);
+        let defender = Stats::new(0, 1);
+        let mut health = Health::new(10);
+        let mut rng = StdRng::seed_from_u64(5);
+        let killed = (0..100).any(|_| {
+            matches!(
+                melee_attack(&attacker, &defender, &mut health, &mut rng),
+                AttackResult::Kill { .. }
+            )
+        });
+        assert!(killed);
+    }
+}
@@ -0,0 +1,329 @@
+pub mod component;
+
+pub use component::{Health, Layer, Renderable};
+
+use crate::{
+    combat::Stats,
+    entity::{
+        monster::{AiState, Monster},
+        player::Player,
+        position::Position,
+    },
+    item::{Inventory, Item},
+};
+use serde::{Deserialize, Serialize};
+
+/// A handle to something in the game: the player, a monster or an item lying
+/// on the floor
+///
+/// An entity is nothing but an id; what it is and does comes from the
+/// components stored against that id in `Entities`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Entity(u32);
+
+impl Entity {
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// One kind of component for every entity that has it, indexed by entity id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Storage<T> {
+    slots: Vec<Option<T>>,
+}
+
+impl<T> Storage<T> {
+    /// Gives `entity` this component, replacing any it already had
+    pub fn insert(&mut self, entity: Entity, component: T) {
+        if self.slots.len() <= entity.index() {
+            self.slots.resize_with(entity.index() + 1, || None);
+        }
+        self.slots[entity.index()] = Some(component);
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.slots.get_mut(entity.index()).and_then(Option::take)
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.slots.get(entity.index()).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.slots.get_mut(entity.index()).and_then(Option::as_mut)
+    }
+
+    /// Every entity with this component, in the order they were spawned
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|c| (Entity(index as u32), c)))
+    }
+
+    /// Moves the component of `entity`, if it has one, onto `into` in `other`
+    fn move_to(&mut self, entity: Entity, other: &mut Storage<T>, into: Entity) {
+        if let Some(component) = self.remove(entity) {
+            other.insert(into, component);
+        }
+    }
+}
+
+// Not derived: that would needlessly require `T: Default`
+impl<T> Default for Storage<T> {
+    fn default() -> Self {
+        Storage { slots: Vec::new() }
+    }
+}
+
+/// Every entity in the game and their components
+///
+/// A hand-rolled entity-component store: each component type has its own
+/// `Storage`, and an entity has a component when there is one stored against
+/// its id. Systems are ordinary functions that look up the storages they need.
+///
+/// ```text
+///                position renderable health stats ai monster item inventory player
+///   Entity(0)       ●         ●        ●      ●                      ●        ●
+///   Entity(1)       ●         ●        ●      ●    ●    ●
+///   Entity(2)       ●         ●                              ●
+/// ```
+///
+/// The store holds the player and whatever shares the current floor with
+/// them; the monsters and items of other floors wait in their `Dungeon` (see
+/// `split_off` and `append`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Entities {
+    /// Id to hand out to the next spawned entity; ids are never reused
+    next_id: u32,
+    pub position: Storage<Position>,
+    pub renderable: Storage<Renderable>,
+    pub health: Storage<Health>,
+    /// Attack and defense, for anything that fights
+    pub stats: Storage<Stats>,
+    pub ai: Storage<AiState>,
+    /// What kind of yokai a monster is and how much energy it has saved up
+    pub monster: Storage<Monster>,
+    /// An item lying on the floor, ready to be picked up
+    pub item: Storage<Item>,
+    pub inventory: Storage<Inventory>,
+    /// Progression, spells, equipment and status of the player character
+    pub player: Storage<Player>,
+}
+
+impl Entities {
+    /// Creates a new entity with no components
+    pub fn spawn(&mut self) -> Entity {
+        let entity = Entity(self.next_id);
+        self.next_id += 1;
+        entity
+    }
+
+    /// Removes `entity` and all of its components
+    pub fn despawn(&mut self, entity: Entity) {
+        self.position.remove(entity);
+        self.renderable.remove(entity);
+        self.health.remove(entity);
+        self.stats.remove(entity);
+        self.ai.remove(entity);
+        self.monster.remove(entity);
+        self.item.remove(entity);
+        self.inventory.remove(entity);
+        self.player.remove(entity);
+    }
+
+    /// Moves every entity but `keep` into a new store, as when the player
+    /// leaves a floor and its monsters and items stay behind
+    pub fn split_off(&mut self, keep: Entity) -> Entities {
+        let mut split = Entities::default();
+        for entity in self.alive() {
+            if entity != keep {
+                let into = split.spawn();
+                self.move_entity(entity, &mut split, into);
+            }
+        }
+        split
+    }
+
+    /// Moves every entity of `other` into this store under new ids
+    pub fn append(&mut self, mut other: Entities) {
+        for entity in other.alive() {
+            let into = self.spawn();
+            other.move_entity(entity, self, into);
+        }
+    }
+
+    /// Every monster, in the order they were spawned
+    pub fn monsters(&self) -> Vec<Entity> {
+        self.monster.iter().map(|(entity, _)| entity).collect()
+    }
+
+    /// The monster standing on `pos`, if any
+    pub fn monster_at(&self, pos: Position) -> Option<Entity> {
+        self.monster
+            .iter()
+            .map(|(entity, _)| entity)
+            .find(|&entity| self.position.get(entity) == Some(&pos))
+    }
+
+    /// The topmost item lying at `pos`, if any
+    pub fn item_at(&self, pos: Position) -> Option<Entity> {
+        self.item
+            .iter()
+            .map(|(entity, _)| entity)
+            .filter(|&entity| self.position.get(entity) == Some(&pos))
+            .last()
+    }
+
+    /// What to draw at `pos`, if a renderable entity stands there
+    ///
+    /// Of several entities on the same tile, the one on the highest `Layer`
+    /// is drawn, and of those the one spawned last.
+    pub fn renderable_at(&self, pos: Position) -> Option<&Renderable> {
+        self.renderable
+            .iter()
+            .filter(|&(entity, _)| self.position.get(entity) == Some(&pos))
+            .map(|(_, renderable)| renderable)
+            .max_by_key(|renderable| renderable.layer)
+    }
+
+    /// Entities that have at least one component, in the order they were spawned
+    fn alive(&self) -> Vec<Entity> {
+        (0..self.next_id)
+            .map(Entity)
+            .filter(|&entity| {
+                self.position.get(entity).is_some()
+                    || self.renderable.get(entity).is_some()
+                    || self.health.get(entity).is_some()
+                    || self.stats.get(entity).is_some()
+                    || self.ai.get(entity).is_some()
+                    || self.monster.get(entity).is_some()
+                    || self.item.get(entity).is_some()
+                    || self.inventory.get(entity).is_some()
+                    || self.player.get(entity).is_some()
+            })
+            .collect()
+    }
+
+    /// Moves every component of `entity` onto `into` in `other`
+    fn move_entity(&mut self, entity: Entity, other: &mut Entities, into: Entity) {
+        self.position.move_to(entity, &mut other.position, into);
+        self.renderable.move_to(entity, &mut other.renderable, into);
+        self.health.move_to(entity, &mut other.health, into);
+        self.stats.move_to(entity, &mut other.stats, into);
+        self.ai.move_to(entity, &mut other.ai, into);
+        self.monster.move_to(entity, &mut other.monster, into);
+        self.item.move_to(entity, &mut other.item, into);
+        self.inventory.move_to(entity, &mut other.inventory, into);
+        self.player.move_to(entity, &mut other.player, into);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entity::monster::MonsterKind, item::ItemKind};
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_spawned_entities_are_distinct() {
+        let mut entities = Entities::default();
+        assert_ne!(entities.spawn(), entities.spawn());
+    }
+
+    #[test]
+    fn test_components_belong_to_their_entity() {
+        let mut entities = Entities::default();
+        let a = entities.spawn();
+        let b = entities.spawn();
+        entities.position.insert(b, Position { x: 3, y: 4 });
+
+        assert_eq!(entities.position.get(a), None);
+        assert_eq!(entities.position.get(b), Some(&Position { x: 3, y: 4 }));
+        assert_eq!(entities.position.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_despawn_removes_every_component() {
+        let mut entities = Entities::default();
+        let entity = entities.spawn();
+        entities.position.insert(entity, Position { x: 1, y: 1 });
+        entities.health.insert(entity, Health::new(5));
+
+        entities.despawn(entity);
+
+        assert_eq!(entities.position.get(entity), None);
+        assert_eq!(entities.health.get(entity), None);
+    }
+
+    #[test]
+    fn test_renderable_at_needs_a_position() {
+        let mut entities = Entities::default();
+        let placed = entities.spawn();
+        let unplaced = entities.spawn();
+        let pos = Position { x: 2, y: 2 };
+        entities.position.insert(placed, pos);
+        entities
+            .renderable
+            .insert(placed, Renderable::new('@', Color::Yellow, Layer::Actor));
+        entities
+            .renderable
+            .insert(unplaced, Renderable::new('x', Color::Red, Layer::Actor));
+
+        assert_eq!(entities.renderable_at(pos).map(|r| r.glyph), Some('@'));
+        assert!(entities.renderable_at(Position { x: 0, y: 0 }).is_none());
+    }
+
+    #[test]
+    fn test_actors_are_drawn_over_items() {
+        let mut entities = Entities::default();
+        let pos = Position { x: 2, y: 2 };
+        let monster = Monster::spawn(&mut entities, MonsterKind::Kappa, pos);
+        Item::spawn(&mut entities, Item::new(ItemKind::Ofuda), pos);
+
+        assert_eq!(entities.renderable_at(pos).map(|r| r.glyph), Some('k'));
+        entities.despawn(monster);
+        assert_eq!(
+            entities.renderable_at(pos).map(|r| r.glyph),
+            Some(ItemKind::Ofuda.category().glyph())
+        );
+    }
+
+    #[test]
+    fn test_item_at_returns_topmost() {
+        let mut entities = Entities::default();
+        let pos = Position { x: 4, y: 4 };
+        assert!(entities.item_at(pos).is_none());
+        Item::spawn(&mut entities, Item::new(ItemKind::Tanto), pos);
+        let top = Item::spawn(&mut entities, Item::new(ItemKind::Ofuda), pos);
+        assert_eq!(entities.item_at(pos), Some(top));
+    }
+
+    #[test]
+    fn test_split_off_and_append_move_a_floor() {
+        let mut entities = Entities::default();
+        let player = Player::spawn(&mut entities, Position { x: 1, y: 1 });
+        let pos = Position { x: 3, y: 3 };
+        Monster::spawn(&mut entities, MonsterKind::Oni, pos);
+        Item::spawn(&mut entities, Item::new(ItemKind::Tanto), pos);
+
+        let floor = entities.split_off(player);
+        assert!(entities.monsters().is_empty());
+        assert!(entities.item_at(pos).is_none());
+        assert!(entities.player.get(player).is_some());
+
+        entities.append(floor);
+        let monster = entities.monster_at(pos).expect("The oni came back");
+        assert_ne!(monster, player);
+        assert_eq!(
+            entities.monster.get(monster).map(|m| m.kind),
+            Some(MonsterKind::Oni)
+        );
+        let item = entities.item_at(pos).expect("The tanto came back");
+        assert_eq!(
+            entities.item.get(item).map(|i| i.kind),
+            Some(ItemKind::Tanto)
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// How an entity looks on the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Renderable {
+    pub glyph: char,
+    #[serde(with = "crate::save::color")]
+    pub color: Color,
+    pub layer: Layer,
+}
+
+impl Renderable {
+    pub fn new(glyph: char, color: Color, layer: Layer) -> Self {
+        Renderable {
+            glyph,
+            color,
+            layer,
+        }
+    }
+}
+
+/// Which of several entities sharing a tile is drawn: the highest layer wins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Layer {
+    /// Things lying on the floor
+    Item,
+    /// The player and monsters, who stand over whatever lies there
+    Actor,
+}
+
+/// Hit points of anything that can be hurt and killed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Health {
+    pub hp: i32,
+    pub max_hp: i32,
+}
+
+impl Health {
+    /// Creates health at full HP
+    pub fn new(max_hp: i32) -> Self {
+        Health { hp: max_hp, max_hp }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.hp <= 0
+    }
+
+    /// Restores up to `amount` HP without exceeding `max_hp`, returning the amount healed
+    pub fn heal(&mut self, amount: i32) -> i32 {
+        let healed = amount.min(self.max_hp - self.hp).max(0);
+        self.hp += healed;
+        healed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_starts_full() {
+        let health = Health::new(20);
+        assert_eq!(health.hp, 20);
+        assert!(!health.is_dead());
+    }
+
+    #[test]
+    fn test_heal_is_capped_at_max_hp() {
+        let mut health = Health::new(20);
+        health.hp = 15;
+        assert_eq!(health.heal(10), 5);
+        assert_eq!(health.hp, 20);
+    }
+}
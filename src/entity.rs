@@ -1,5 +1,8 @@
+pub mod monster;
 pub mod player;
 pub mod position;
+pub mod status;
 
+pub use monster::Monster;
 pub use player::Player;
 pub use position::Position;
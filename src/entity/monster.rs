@@ -0,0 +1,283 @@
+use crate::{
+    combat::Stats,
+    ecs::{Entities, Entity, Health, Layer, Renderable},
+    entity::position::Position,
+    game::{
+        config::MAX_DEPTH,
+        turn::{Energy, NORMAL_SPEED},
+    },
+    world::tile::Movement,
+};
+use rand::{Rng, seq::SliceRandom};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// Behaviour state of a monster's AI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AiState {
+    /// Stands still until the player comes into view
+    Idle,
+    /// Roams randomly around the floor
+    Wander,
+    /// Moves toward the player
+    Chase,
+    /// Moves away from the player (low HP)
+    Flee,
+}
+
+/// The kinds of yokai that roam the dungeon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MonsterKind {
+    /// 河童 - river imp
+    Kappa,
+    /// 狐 - trickster fox spirit
+    Kitsune,
+    /// 天狗 - mountain goblin
+    Tengu,
+    /// ろくろ首 - long-necked woman
+    Rokurokubi,
+    /// 鬼 - ogre
+    Oni,
+    /// ぬらりひょん - leader of the night parade, met only on the deepest floor
+    Nurarihyon,
+}
+
+impl MonsterKind {
+    /// Kinds that spawn at random; the boss is placed by hand in its arena
+    pub const ALL: [MonsterKind; 5] = [
+        MonsterKind::Kappa,
+        MonsterKind::Kitsune,
+        MonsterKind::Tengu,
+        MonsterKind::Rokurokubi,
+        MonsterKind::Oni,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MonsterKind::Kappa => "Kappa",
+            MonsterKind::Kitsune => "Kitsune",
+            MonsterKind::Tengu => "Tengu",
+            MonsterKind::Rokurokubi => "Rokurokubi",
+            MonsterKind::Oni => "Oni",
+            MonsterKind::Nurarihyon => "Nurarihyon",
+        }
+    }
+
+    pub fn glyph(&self) -> char {
+        match self {
+            MonsterKind::Kappa => 'k',
+            MonsterKind::Kitsune => 'f',
+            MonsterKind::Tengu => 't',
+            MonsterKind::Rokurokubi => 'r',
+            MonsterKind::Oni => 'O',
+            MonsterKind::Nurarihyon => 'N',
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            MonsterKind::Kappa => Color::Green,
+            MonsterKind::Kitsune => Color::LightYellow,
+            MonsterKind::Tengu => Color::Red,
+            MonsterKind::Rokurokubi => Color::Magenta,
+            MonsterKind::Oni => Color::LightRed,
+            MonsterKind::Nurarihyon => Color::LightMagenta,
+        }
+    }
+
+    pub fn max_hp(&self) -> i32 {
+        match self {
+            MonsterKind::Kappa => 6,
+            MonsterKind::Kitsune => 8,
+            MonsterKind::Tengu => 10,
+            MonsterKind::Rokurokubi => 12,
+            MonsterKind::Oni => 18,
+            MonsterKind::Nurarihyon => 60,
+        }
+    }
+
+    /// Base combat stats (attack, defense)
+    pub fn stats(&self) -> Stats {
+        match self {
+            MonsterKind::Kappa => Stats::new(3, 0),
+            MonsterKind::Kitsune => Stats::new(4, 1),
+            MonsterKind::Tengu => Stats::new(5, 2),
+            MonsterKind::Rokurokubi => Stats::new(6, 2),
+            MonsterKind::Oni => Stats::new(8, 3),
+            MonsterKind::Nurarihyon => Stats::new(11, 5),
+        }
+    }
+
+    /// Experience awarded for defeating this kind
+    pub fn xp(&self) -> u32 {
+        match self {
+            MonsterKind::Kappa => 5,
+            MonsterKind::Kitsune => 7,
+            MonsterKind::Tengu => 12,
+            MonsterKind::Rokurokubi => 18,
+            MonsterKind::Oni => 30,
+            MonsterKind::Nurarihyon => 100,
+        }
+    }
+
+    /// Mon dropped when this kind is defeated
+    pub fn gold(&self) -> u32 {
+        match self {
+            MonsterKind::Kappa => 2,
+            MonsterKind::Kitsune => 6,
+            MonsterKind::Tengu => 8,
+            MonsterKind::Rokurokubi => 10,
+            MonsterKind::Oni => 20,
+            MonsterKind::Nurarihyon => 200,
+        }
+    }
+
+    /// Shallowest depth at which this kind can appear
+    pub fn min_depth(&self) -> u32 {
+        match self {
+            MonsterKind::Kappa => 1,
+            MonsterKind::Kitsune => 1,
+            MonsterKind::Tengu => 3,
+            MonsterKind::Rokurokubi => 5,
+            MonsterKind::Oni => 7,
+            MonsterKind::Nurarihyon => MAX_DEPTH,
+        }
+    }
+
+    /// How this kind gets about: kappa swim and tengu fly, the rest walk
+    pub fn movement(&self) -> Movement {
+        match self {
+            MonsterKind::Kappa => Movement::Swim,
+            MonsterKind::Tengu => Movement::Fly,
+            _ => Movement::Walk,
+        }
+    }
+
+    /// Energy gained each tick: tengu are twice as quick as the player,
+    /// kitsune a little quicker and oni lumber along
+    pub fn speed(&self) -> i32 {
+        match self {
+            MonsterKind::Kitsune => 12,
+            MonsterKind::Tengu => 20,
+            MonsterKind::Oni => 8,
+            _ => NORMAL_SPEED,
+        }
+    }
+
+    /// Whether this is the boss guarding the deepest floor
+    pub fn is_boss(&self) -> bool {
+        *self == MonsterKind::Nurarihyon
+    }
+
+    /// Picks a random kind that is allowed to appear at `depth`
+    pub fn random_for_depth<R: Rng>(depth: u32, rng: &mut R) -> MonsterKind {
+        let candidates: Vec<MonsterKind> = Self::ALL
+            .into_iter()
+            .filter(|kind| kind.min_depth() <= depth)
+            .collect();
+        *candidates
+            .choose(rng)
+            .expect("At least one monster kind must be available at depth 1")
+    }
+}
+
+/// What makes an entity a yokai roaming the dungeon
+///
+/// Where it stands, how it looks, its HP and stats and what its AI is up to
+/// are ordinary components of the same entity (see `Monster::spawn`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Monster {
+    pub kind: MonsterKind,
+    pub energy: Energy,
+}
+
+impl Monster {
+    pub fn new(kind: MonsterKind) -> Self {
+        Monster {
+            kind,
+            energy: Energy::default(),
+        }
+    }
+
+    /// Spawns a monster of `kind` at `position`, at full HP and idle
+    pub fn spawn(entities: &mut Entities, kind: MonsterKind, position: Position) -> Entity {
+        let monster = entities.spawn();
+        entities.position.insert(monster, position);
+        entities.renderable.insert(
+            monster,
+            Renderable::new(kind.glyph(), kind.color(), Layer::Actor),
+        );
+        entities.health.insert(monster, Health::new(kind.max_hp()));
+        entities.stats.insert(monster, kind.stats());
+        entities.ai.insert(monster, AiState::Idle);
+        entities.monster.insert(monster, Monster::new(kind));
+        monster
+    }
+
+    /// Whether `health` has dropped low enough that the monster wants to
+    /// run away
+    ///
+    /// The boss never runs.
+    pub fn is_badly_hurt(&self, health: &Health) -> bool {
+        !self.kind.is_boss() && health.hp * 4 <= health.max_hp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_monster_creation() {
+        let mut entities = Entities::default();
+        let pos = Position { x: 3, y: 4 };
+        let monster = Monster::spawn(&mut entities, MonsterKind::Kappa, pos);
+
+        assert_eq!(entities.position.get(monster), Some(&pos));
+        assert_eq!(entities.renderable_at(pos).map(|r| r.glyph), Some('k'));
+        assert_eq!(
+            entities.health.get(monster),
+            Some(&Health::new(MonsterKind::Kappa.max_hp()))
+        );
+        assert_eq!(entities.ai.get(monster), Some(&AiState::Idle));
+    }
+
+    #[test]
+    fn test_badly_hurt_threshold() {
+        let oni = Monster::new(MonsterKind::Oni);
+        let mut health = Health::new(MonsterKind::Oni.max_hp());
+        assert!(!oni.is_badly_hurt(&health));
+        health.hp = health.max_hp / 4;
+        assert!(oni.is_badly_hurt(&health));
+    }
+
+    #[test]
+    fn test_boss_never_flees() {
+        let boss = Monster::new(MonsterKind::Nurarihyon);
+        let mut health = Health::new(MonsterKind::Nurarihyon.max_hp());
+        health.hp = 1;
+        assert!(!boss.is_badly_hurt(&health));
+    }
+
+    #[test]
+    fn test_boss_never_spawns_randomly() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            assert!(!MonsterKind::random_for_depth(MAX_DEPTH, &mut rng).is_boss());
+        }
+    }
+
+    #[test]
+    fn test_random_kind_respects_min_depth() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let kind = MonsterKind::random_for_depth(1, &mut rng);
+            assert!(
+                kind.min_depth() <= 1,
+                "{:?} should not appear at depth 1",
+                kind
+            );
+        }
+    }
+}
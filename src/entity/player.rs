@@ -1,32 +1,214 @@
-use crate::entity::position::Position;
+use crate::{
+    combat::Stats,
+    ecs::{Entities, Entity, Health, Layer, Renderable},
+    entity::{position::Position, status::StatusEffects},
+    game::{
+        config::{
+            INVENTORY_CAPACITY, LEVEL_UP_ATTACK, LEVEL_UP_HP, PLAYER_ATTACK, PLAYER_DEFENSE,
+            PLAYER_MAX_HP, PLAYER_MAX_MANA, XP_PER_LEVEL,
+        },
+        turn::{Energy, NORMAL_SPEED, effective_speed},
+    },
+    item::{Inventory, Item, ItemCategory},
+    spell::{Spell, SpellBook},
+    world::tile::Movement,
+};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 
+/// What only the player character has: progression, spells, equipment and
+/// status
+///
+/// Where the player stands, their HP and stats and their pack are ordinary
+/// components of the player entity (see `Player::spawn`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Player {
-    pub position: Position,
+    pub weapon: Option<Item>,
+    pub armour: Option<Item>,
+    pub spellbook: SpellBook,
+    pub level: u32,
+    /// Experience gained towards the next level
+    pub xp: u32,
+    /// Mon (文) collected from defeated yokai
+    pub gold: u32,
+    pub status: StatusEffects,
+    pub energy: Energy,
 }
 
 impl Player {
-    pub fn new(position: Position) -> Self {
-        Player { position }
+    pub fn new() -> Self {
+        Player {
+            weapon: None,
+            armour: None,
+            spellbook: SpellBook::new(Spell::ALL.to_vec(), PLAYER_MAX_MANA),
+            level: 1,
+            xp: 0,
+            gold: 0,
+            status: StatusEffects::default(),
+            energy: Energy::ready(),
+        }
     }
 
-    pub fn to_char(&self) -> char {
-        '@'
+    /// Spawns a fresh player character at `position`: drawn as a yellow `@`,
+    /// with starting stats and an empty pack
+    pub fn spawn(entities: &mut Entities, position: Position) -> Entity {
+        let player = entities.spawn();
+        entities.position.insert(player, position);
+        entities
+            .renderable
+            .insert(player, Renderable::new('@', Color::Yellow, Layer::Actor));
+        entities.health.insert(player, Health::new(PLAYER_MAX_HP));
+        entities
+            .stats
+            .insert(player, Stats::new(PLAYER_ATTACK, PLAYER_DEFENSE));
+        entities
+            .inventory
+            .insert(player, Inventory::new(INVENTORY_CAPACITY));
+        entities.player.insert(player, Player::new());
+        player
+    }
+
+    /// The player always goes on foot
+    pub fn movement(&self) -> Movement {
+        Movement::Walk
+    }
+
+    /// Energy gained each tick, quickened by haste and dragged down by slow
+    pub fn speed(&self) -> i32 {
+        effective_speed(NORMAL_SPEED, &self.status)
+    }
+
+    /// Experience needed to go from the current level to the next
+    pub fn xp_to_next_level(&self) -> u32 {
+        self.level * XP_PER_LEVEL
+    }
+
+    /// Adds experience, levelling up as many times as it allows
+    ///
+    /// Each level raises the player's max HP (healing by the same amount)
+    /// and attack. Returns the number of levels gained.
+    pub fn gain_xp(&mut self, amount: u32, health: &mut Health, stats: &mut Stats) -> u32 {
+        self.xp += amount;
+        let mut gained = 0;
+        while self.xp >= self.xp_to_next_level() {
+            self.xp -= self.xp_to_next_level();
+            self.level += 1;
+            health.max_hp += LEVEL_UP_HP;
+            health.hp += LEVEL_UP_HP;
+            stats.attack += LEVEL_UP_ATTACK;
+            gained += 1;
+        }
+        gained
+    }
+
+    /// Equips a weapon or armour, returning whatever was previously in that slot
+    ///
+    /// Equipment bonuses are applied directly to the player's `stats` so that
+    /// combat only ever needs to look at `Stats`.
+    ///
+    /// # Panics
+    /// Panics if `item` is not a weapon or armour.
+    pub fn equip(&mut self, item: Item, stats: &mut Stats) -> Option<Item> {
+        let slot = match item.category() {
+            ItemCategory::Weapon => &mut self.weapon,
+            ItemCategory::Armour => &mut self.armour,
+            other => panic!("{:?} cannot be equipped", other),
+        };
+
+        let previous = slot.replace(item);
+        if let Some(old) = previous {
+            stats.attack -= old.kind.attack_bonus();
+            stats.defense -= old.kind.defense_bonus();
+        }
+        stats.attack += item.kind.attack_bonus();
+        stats.defense += item.kind.defense_bonus();
+        previous
+    }
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::item::ItemKind;
+
+    fn starting_stats() -> Stats {
+        Stats::new(PLAYER_ATTACK, PLAYER_DEFENSE)
+    }
 
     #[test]
     fn test_player_creation() {
-        let player = Player::new(Position { x: 5, y: 5 });
-        assert_eq!(player.position, Position { x: 5, y: 5 });
+        let mut entities = Entities::default();
+        let player = Player::spawn(&mut entities, Position { x: 5, y: 5 });
+
+        assert_eq!(
+            entities.position.get(player),
+            Some(&Position { x: 5, y: 5 })
+        );
+        assert_eq!(
+            entities.health.get(player).map(|h| h.hp),
+            Some(PLAYER_MAX_HP)
+        );
+        assert!(entities.inventory.get(player).is_some_and(|i| i.is_empty()));
+        assert_eq!(entities.player.get(player).map(|p| p.level), Some(1));
+    }
+
+    #[test]
+    fn test_gain_xp_levels_up() {
+        let mut player = Player::new();
+        let mut health = Health::new(PLAYER_MAX_HP);
+        let mut stats = starting_stats();
+        assert_eq!(player.gain_xp(XP_PER_LEVEL - 1, &mut health, &mut stats), 0);
+        assert_eq!(player.level, 1);
+
+        assert_eq!(player.gain_xp(2, &mut health, &mut stats), 1);
+        assert_eq!(player.level, 2);
+        assert_eq!(player.xp, 1);
+        assert_eq!(health.max_hp, PLAYER_MAX_HP + LEVEL_UP_HP);
+        assert_eq!(stats.attack, PLAYER_ATTACK + LEVEL_UP_ATTACK);
+    }
+
+    #[test]
+    fn test_gain_xp_can_skip_levels() {
+        let mut player = Player::new();
+        // Level 1 -> 2 takes 1x, level 2 -> 3 takes 2x
+        let mut health = Health::new(PLAYER_MAX_HP);
+        assert_eq!(
+            player.gain_xp(XP_PER_LEVEL * 3, &mut health, &mut starting_stats()),
+            2
+        );
+        assert_eq!(player.level, 3);
+        assert_eq!(player.xp, 0);
     }
 
     #[test]
     fn test_player_symbol() {
-        let player = Player::new(Position { x: 0, y: 0 });
-        assert_eq!(player.to_char(), '@');
+        let mut entities = Entities::default();
+        let pos = Position { x: 0, y: 0 };
+        Player::spawn(&mut entities, pos);
+        assert_eq!(entities.renderable_at(pos).map(|r| r.glyph), Some('@'));
+    }
+
+    #[test]
+    fn test_equip_weapon_adds_attack() {
+        let mut player = Player::new();
+        let mut stats = starting_stats();
+        assert_eq!(player.equip(Item::new(ItemKind::Katana), &mut stats), None);
+        assert_eq!(stats.attack, PLAYER_ATTACK + 3);
+    }
+
+    #[test]
+    fn test_equip_swaps_previous_item() {
+        let mut player = Player::new();
+        let mut stats = starting_stats();
+        player.equip(Item::new(ItemKind::Haori), &mut stats);
+        let previous = player.equip(Item::new(ItemKind::Yoroi), &mut stats);
+        assert_eq!(previous, Some(Item::new(ItemKind::Haori)));
+        assert_eq!(stats.defense, PLAYER_DEFENSE + 3);
     }
 }
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
@@ -11,6 +13,13 @@ impl Position {
             y: self.y + dy,
         }
     }
+
+    /// Squared Euclidean distance (avoids floating point for range checks)
+    pub fn distance_squared(self, other: Position) -> i32 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        dx * dx + dy * dy
+    }
 }
 
 #[cfg(test)]
@@ -42,4 +51,13 @@ mod tests {
         assert_eq!(pos.translate(0, -1), Position { x: 5, y: 9 });
         assert_eq!(pos.translate(3, -2), Position { x: 8, y: 8 });
     }
+
+    #[test]
+    fn test_position_distance_squared() {
+        let a = Position { x: 1, y: 1 };
+        let b = Position { x: 4, y: 5 };
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!(b.distance_squared(a), 25);
+        assert_eq!(a.distance_squared(a), 0);
+    }
 }
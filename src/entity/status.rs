@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+/// Temporary conditions affecting the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusKind {
+    /// Recovers 1 HP at the end of every turn
+    Regenerating,
+    /// Loses HP at the end of every turn until it wears off or is put out
+    Burning,
+    /// Holding breath in deep water; once it wears off, the swimmer starts drowning
+    Swimming,
+    /// Loses HP at the end of every turn until it wears off
+    Poisoned,
+    /// Acts twice as often
+    Hasted,
+    /// Acts half as often
+    Slowed,
+}
+
+impl StatusKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusKind::Regenerating => "Regenerating",
+            StatusKind::Burning => "Burning",
+            StatusKind::Swimming => "Swimming",
+            StatusKind::Poisoned => "Poisoned",
+            StatusKind::Hasted => "Hasted",
+            StatusKind::Slowed => "Slowed",
+        }
+    }
+
+    /// Whether the effect is bad news, for colouring it in the status panel
+    pub fn is_harmful(&self) -> bool {
+        matches!(
+            self,
+            StatusKind::Burning | StatusKind::Swimming | StatusKind::Poisoned | StatusKind::Slowed
+        )
+    }
+}
+
+/// An active condition and how many more turns it lasts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub turns_left: u32,
+}
+
+/// The conditions currently affecting a creature
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Applies `kind` for `turns` turns; reapplying an active effect extends it
+    pub fn add(&mut self, kind: StatusKind, turns: u32) {
+        match self.effects.iter_mut().find(|e| e.kind == kind) {
+            Some(effect) => effect.turns_left = effect.turns_left.max(turns),
+            None => self.effects.push(StatusEffect {
+                kind,
+                turns_left: turns,
+            }),
+        }
+    }
+
+    /// Ends `kind` early, returning whether it was active
+    pub fn remove(&mut self, kind: StatusKind) -> bool {
+        let before = self.effects.len();
+        self.effects.retain(|e| e.kind != kind);
+        self.effects.len() < before
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects.iter().any(|e| e.kind == kind)
+    }
+
+    /// Counts down every effect by one turn, returning the ones that wore off
+    pub fn tick(&mut self) -> Vec<StatusKind> {
+        for effect in &mut self.effects {
+            effect.turns_left = effect.turns_left.saturating_sub(1);
+        }
+        let expired = self
+            .effects
+            .iter()
+            .filter(|e| e.turns_left == 0)
+            .map(|e| e.kind)
+            .collect();
+        self.effects.retain(|e| e.turns_left > 0);
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reapplying_extends_duration() {
+        let mut status = StatusEffects::default();
+        status.add(StatusKind::Regenerating, 3);
+        status.add(StatusKind::Regenerating, 5);
+        status.add(StatusKind::Regenerating, 2);
+
+        assert_eq!(status.effects.len(), 1);
+        assert_eq!(status.effects[0].turns_left, 5);
+    }
+
+    #[test]
+    fn test_effects_expire() {
+        let mut status = StatusEffects::default();
+        status.add(StatusKind::Regenerating, 2);
+
+        assert!(status.tick().is_empty());
+        assert!(status.has(StatusKind::Regenerating));
+        assert_eq!(status.tick(), vec![StatusKind::Regenerating]);
+        assert!(!status.has(StatusKind::Regenerating));
+    }
+
+    #[test]
+    fn test_remove_ends_effect() {
+        let mut status = StatusEffects::default();
+        status.add(StatusKind::Burning, 3);
+
+        assert!(status.remove(StatusKind::Burning));
+        assert!(!status.has(StatusKind::Burning));
+        assert!(!status.remove(StatusKind::Burning));
+    }
+}
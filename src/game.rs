@@ -1,54 +1,683 @@
+pub mod ai;
 pub mod config;
+pub mod doors;
+pub mod ending;
+pub mod items;
+pub mod magic;
+pub mod messages;
+pub mod search;
 pub mod state;
+pub mod terrain;
+pub mod traps;
+pub mod turn;
 
 use crate::{
-    entity::player::Player,
+    combat::{self, AttackResult, Stats},
+    ecs::{Entities, Entity, Health, Renderable},
+    entity::{monster::MonsterKind, player::Player, position::Position, status::StatusKind},
+    item::{Inventory, Item, ItemKind},
+    menu::MainMenu,
+    message::{MessageKind, MessageLog},
+    options::Options,
+    spell::Targeting,
     world::{dungeon::Dungeon, tile::TileType},
 };
-use config::MAX_DEPTH;
+use ai::MonsterAction;
+use config::{MANA_REGEN_INTERVAL, MAX_DEPTH, MESSAGE_LOG_CAPACITY, POISON_DAMAGE};
+use ending::RunEnd;
+use rand::{Rng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
 use state::GameState;
+use std::{collections::BTreeMap, path::PathBuf};
+use turn::{ActionKind, Actor, Actors, Energy, Next, Scheduler};
 
+#[derive(Serialize, Deserialize)]
 pub struct Game {
     pub state: GameState,
+    /// The floor the player is on
     pub dungeon: Dungeon,
-    pub player: Player,
+    /// Floors the player has left, by depth, kept exactly as they were
+    pub levels: BTreeMap<u32, Dungeon>,
+    /// The player, who carries over between floors, and the monsters and
+    /// items of the current floor
+    pub entities: Entities,
+    /// The player character in `entities`
+    pub player_id: Entity,
+    /// Not saved; a loaded game is always running
+    #[serde(skip)]
     pub running: bool,
+    /// Selected row in the inventory screen
+    pub inventory_cursor: usize,
+    /// Selected row in the spell menu
+    pub spell_cursor: usize,
+    /// The spell being aimed while in `GameState::Targeting`
+    pub targeting: Option<Targeting>,
+    /// Number of turns that have passed
+    pub turn: u64,
+    /// Decides whose action comes next as their energy allows
+    pub scheduler: Scheduler,
+    /// Master seed of the run; every floor is generated from it
+    pub seed: u64,
+    /// Monsters the player has killed this run
+    pub kills: u32,
+    /// How the run ended, once it has
+    pub run_end: Option<RunEnd>,
+    /// Everything that has happened, shown beneath the map
+    pub messages: MessageLog,
+    /// How far the message history screen is scrolled back
+    pub message_scroll: usize,
+    /// Title screen state while in `GameState::MainMenu`; not saved
+    #[serde(skip)]
+    pub menu: MainMenu,
+    /// Player preferences; stored separately from the run
+    #[serde(skip)]
+    pub options: Options,
+    /// Where the morgue file of the finished run was written; not saved
+    #[serde(skip)]
+    pub morgue_path: Option<PathBuf>,
 }
 
 impl Game {
+    /// Starts a new run with a random seed
     pub fn new() -> Self {
-        let dungeon = Dungeon::new_random(1);
-        let player = Player::new(dungeon.player_start);
+        Self::with_seed(rand::random())
+    }
+
+    /// Starts a new run whose floors are generated from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        let mut dungeon = Dungeon::new_seeded(1, seed);
+        let mut entities = Entities::default();
+        let player_id = Player::spawn(&mut entities, dungeon.player_start);
+        entities.append(std::mem::take(&mut dungeon.population));
 
-        Game {
+        let mut game = Game {
             state: GameState::default(),
             dungeon,
-            player,
+            levels: BTreeMap::new(),
+            entities,
+            player_id,
             running: true,
+            inventory_cursor: 0,
+            spell_cursor: 0,
+            targeting: None,
+            turn: 0,
+            scheduler: Scheduler::default(),
+            seed,
+            kills: 0,
+            run_end: None,
+            messages: MessageLog::new(MESSAGE_LOG_CAPACITY),
+            message_scroll: 0,
+            menu: MainMenu::default(),
+            options: Options::default(),
+            morgue_path: None,
+        };
+        game.message(
+            MessageKind::Info,
+            "You enter the dungeon of a hundred demons. Bring back the Hyakki Yagyō Emaki.",
+        );
+        game.update_fov();
+        game
+    }
+
+    /// Moves the player, or attacks if a monster occupies the destination
+    ///
+    /// Walking into a closed door opens it, and into a locked one unlocks it
+    /// if the player has a key. Hazard terrain can be walked into at the
+    /// player's own risk (see `enter_terrain`), and stepping onto a trap
+    /// sets it off.
+    ///
+    /// Returns `true` if the action used up the player's turn. Moving costs
+    /// more energy on slow terrain.
+    pub fn try_move_player(&mut self, dx: i32, dy: i32) -> bool {
+        let mut rng = rand::thread_rng();
+        self.try_move_player_with_rng(dx, dy, &mut rng)
+    }
+
+    /// Moves or attacks using a provided RNG (for testing)
+    pub fn try_move_player_with_rng<R: Rng>(&mut self, dx: i32, dy: i32, rng: &mut R) -> bool {
+        let new_pos = self.player_pos().translate(dx, dy);
+
+        if let Some(monster) = self.entities.monster_at(new_pos) {
+            self.player_mut().energy.spend(ActionKind::Attack.cost());
+            self.player_attack(monster, rng);
+            return true;
         }
+
+        match self.dungeon.get_tile(new_pos) {
+            Some(TileType::DoorClosed) => {
+                self.open_door(new_pos);
+                return true;
+            }
+            Some(TileType::DoorLocked) => return self.try_unlock_door(new_pos),
+            _ => {}
+        }
+
+        let movement = self.player().movement();
+        let Some(cost) = self
+            .dungeon
+            .get_tile(new_pos)
+            .and_then(|tile| tile.move_cost(movement))
+        else {
+            return false;
+        };
+
+        let from = self.player_pos();
+        let depth = self.dungeon.depth;
+        self.player_mut()
+            .energy
+            .spend(ActionKind::Move.cost() * cost as i32);
+        self.set_player_pos(new_pos);
+        self.update_fov();
+        self.enter_terrain(from, rng);
+        if !self.is_game_over() && self.dungeon.depth == depth {
+            self.spring_trap(rng);
+        }
+        if self.is_game_over() || self.dungeon.depth != depth || self.player_pos() != new_pos {
+            return true;
+        }
+        self.notice_hidden(rng);
+
+        if self.options.auto_pickup && self.entities.item_at(new_pos).is_some() {
+            self.try_pick_up();
+        }
+        true
     }
 
-    pub fn try_move_player(&mut self, dx: i32, dy: i32) {
-        let new_pos = self.player.position.translate(dx, dy);
+    /// Resolves a melee attack from the player against `monster`
+    fn player_attack<R: Rng>(&mut self, monster: Entity, rng: &mut R) -> AttackResult {
+        let defender = self
+            .entities
+            .stats
+            .get(monster)
+            .expect("Monsters have stats");
+        match combat::roll_melee_damage(self.player_stats(), defender, rng) {
+            Some(damage) => self.damage_monster(monster, damage),
+            None => {
+                let text = format!("You miss the {}.", self.monster_kind(monster).name());
+                self.message(MessageKind::Info, text);
+                AttackResult::Miss
+            }
+        }
+    }
+
+    /// Applies damage to `monster`, removing it if it dies
+    ///
+    /// Every way the player can hurt a monster (melee, items, spells) ends up
+    /// here, so kills are handled the same way regardless of the source.
+    pub(crate) fn damage_monster(&mut self, monster: Entity, damage: i32) -> AttackResult {
+        let kind = self.monster_kind(monster);
+        let health = self
+            .entities
+            .health
+            .get_mut(monster)
+            .expect("Monsters have health");
+        let result = combat::apply_damage(health, damage);
+        match result {
+            AttackResult::Kill { .. } => {
+                let position = self.entities.position.get(monster).copied();
+                self.entities.despawn(monster);
+                self.kills += 1;
+                self.message(MessageKind::Good, format!("You kill the {}!", kind.name()));
+                self.reward_kill(kind);
+                if let Some(position) = position.filter(|_| kind.is_boss()) {
+                    self.drop_artifact(position);
+                }
+            }
+            AttackResult::Hit { damage } => {
+                let text = format!("You hit the {} for {}.", kind.name(), damage);
+                self.message(MessageKind::Info, text);
+            }
+            AttackResult::Miss => {}
+        }
+        result
+    }
+
+    /// Deals `damage` to the player from something other than a monster
+    /// (terrain, traps, poison), ending the run with `cause` if it kills them
+    pub(crate) fn hurt_player(&mut self, damage: i32, cause: &str) {
+        if let AttackResult::Kill { .. } = combat::apply_damage(self.player_health_mut(), damage) {
+            self.message(MessageKind::Bad, "You die...");
+            self.end_run(RunEnd::Perished {
+                cause: cause.to_string(),
+            });
+        }
+    }
 
-        if self.dungeon.is_walkable(new_pos) {
-            self.player.position = new_pos;
+    /// Grants the experience and mon for defeating a monster of `kind`
+    fn reward_kill(&mut self, kind: MonsterKind) {
+        let entities = &mut self.entities;
+        let player = entities
+            .player
+            .get_mut(self.player_id)
+            .expect("The player entity is always a player");
+        let health = entities
+            .health
+            .get_mut(self.player_id)
+            .expect("The player always has health");
+        let stats = entities
+            .stats
+            .get_mut(self.player_id)
+            .expect("The player always has stats");
+        player.gold += kind.gold();
+        if player.gain_xp(kind.xp(), health, stats) > 0 {
+            let text = format!("You reach level {}!", self.player().level);
+            self.message(MessageKind::Good, text);
         }
     }
 
+    /// Finishes the player's action and lets the scheduler run until the
+    /// player can act again, with the monsters acting in between as their
+    /// energy allows
+    ///
+    /// Moving, attacking, casting and taking the stairs spend their own cost
+    /// as they happen; an action that hasn't paid (using an item, opening a
+    /// door, searching) costs an ordinary action here. Every time a turn
+    /// ends, mana regenerates and terrain and status effects apply.
+    ///
+    /// Does nothing once the run is over, and stops as soon as it ends.
+    pub fn end_turn(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.end_turn_with_rng(&mut rng);
+    }
+
+    /// Finishes the player's action using a provided RNG (for testing)
+    pub fn end_turn_with_rng<R: Rng>(&mut self, rng: &mut R) {
+        if self.is_game_over() {
+            return;
+        }
+        if self.player().energy.can_act() {
+            self.player_mut().energy.spend(ActionKind::Other.cost());
+        }
+
+        loop {
+            let mut actors = TurnTakers {
+                entities: &mut self.entities,
+                player_id: self.player_id,
+            };
+            match self.scheduler.next(&mut actors) {
+                Next::Act(Actor::Player) => return,
+                Next::Act(Actor::Monster(monster)) => self.monster_act(monster, rng),
+                Next::TurnEnds => self.pass_turn(),
+            }
+            if self.is_game_over() {
+                return;
+            }
+        }
+    }
+
+    /// Advances the turn counter, regenerates mana and applies terrain and
+    /// status effects
+    fn pass_turn(&mut self) {
+        self.turn += 1;
+        if self.turn.is_multiple_of(MANA_REGEN_INTERVAL) {
+            self.player_mut().spellbook.restore(1);
+        }
+        self.apply_terrain_effects();
+        self.apply_status_effects();
+    }
+
+    /// Applies one turn of every status effect on the player, then counts them down
+    fn apply_status_effects(&mut self) {
+        if self.player().status.has(StatusKind::Regenerating) {
+            self.player_health_mut().heal(1);
+        }
+        if self.player().status.has(StatusKind::Burning) {
+            self.burn();
+        }
+        if self.player().status.has(StatusKind::Poisoned) {
+            self.message(MessageKind::Bad, "The poison burns in your veins.");
+            self.hurt_player(POISON_DAMAGE, "Succumbed to poison");
+        }
+        for kind in self.player_mut().status.tick() {
+            match kind {
+                StatusKind::Swimming => self.message(MessageKind::Bad, "You are out of breath!"),
+                StatusKind::Burning => self.message(MessageKind::Info, "The flames die down."),
+                _ => self.message(MessageKind::Info, format!("{} wears off.", kind.name())),
+            }
+        }
+    }
+
+    /// Leaves the artifact where the boss fell
+    fn drop_artifact(&mut self, position: Position) {
+        let item = Item::new(ItemKind::HyakkiEmaki);
+        Item::spawn(&mut self.entities, item, position);
+        let text = format!(
+            "The {} falls to the floor. Take it to the surface!",
+            item.name()
+        );
+        self.message(MessageKind::Good, text);
+    }
+
+    /// Lets `monster` on the current floor act, paying for what it does
+    fn monster_act<R: Rng>(&mut self, monster: Entity, rng: &mut R) {
+        let player_pos = self.player_pos();
+        let action = ai::take_turn(
+            &mut self.dungeon,
+            &mut self.entities,
+            monster,
+            player_pos,
+            rng,
+        );
+        let kind = self.monster_kind(monster);
+        let cost = match action {
+            MonsterAction::AttackPlayer => ActionKind::Attack.cost(),
+            MonsterAction::Moved => {
+                let terrain = self
+                    .entities
+                    .position
+                    .get(monster)
+                    .and_then(|&pos| self.dungeon.get_tile(pos))
+                    .and_then(|tile| tile.move_cost(kind.movement()))
+                    .unwrap_or(1);
+                ActionKind::Move.cost() * terrain as i32
+            }
+            MonsterAction::Wait | MonsterAction::OpenedDoor => ActionKind::Other.cost(),
+        };
+        if let Some(monster) = self.entities.monster.get_mut(monster) {
+            monster.energy.spend(cost);
+        }
+
+        if action == MonsterAction::AttackPlayer {
+            let name = kind.name();
+            let attacker = *self
+                .entities
+                .stats
+                .get(monster)
+                .expect("Monsters have stats");
+            let defender = *self.player_stats();
+            match combat::melee_attack(&attacker, &defender, self.player_health_mut(), rng) {
+                AttackResult::Miss => {
+                    self.message(MessageKind::Info, format!("The {} misses you.", name));
+                }
+                AttackResult::Hit { damage } => {
+                    let text = format!("The {} hits you for {}.", name, damage);
+                    self.message(MessageKind::Bad, text);
+                }
+                AttackResult::Kill { .. } => {
+                    self.message(MessageKind::Bad, format!("The {} kills you...", name));
+                    self.end_run(RunEnd::Killed {
+                        by: name.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.state == GameState::GameOver
+    }
+
     pub fn quit(&mut self) {
         self.running = false;
     }
 
-    /// Attempts to descend to the next dungeon level if standing on stairs
-    pub fn try_descend(&mut self) {
-        // Check if player is standing on stairs
-        if self.dungeon.get_tile(self.player.position) == Some(TileType::StairsDown) {
-            let new_depth = self.dungeon.depth + 1;
-            if new_depth <= MAX_DEPTH {
-                self.dungeon = Dungeon::new_random(new_depth);
-                self.player.position = self.dungeon.player_start;
-            }
+    /// Takes the down-stairs, arriving on the up-stairs of the floor below
+    ///
+    /// Returns `true` if the player went down, using up their turn.
+    pub fn try_descend(&mut self) -> bool {
+        if self.dungeon.get_tile(self.player_pos()) != Some(TileType::StairsDown) {
+            self.message(MessageKind::Warning, "There are no stairs down here.");
+            return false;
+        }
+        let new_depth = self.dungeon.depth + 1;
+        if new_depth > MAX_DEPTH {
+            self.message(MessageKind::Warning, "The stairs go no deeper.");
+            return false;
+        }
+
+        self.player_mut().energy.spend(ActionKind::Descend.cost());
+        self.change_floor(new_depth);
+        self.set_player_pos(self.dungeon.player_start);
+        self.update_fov();
+        self.message(
+            MessageKind::Info,
+            format!("You descend to depth {}.", new_depth),
+        );
+        if self.entities.monster.iter().any(|(_, m)| m.kind.is_boss()) {
+            self.message(
+                MessageKind::Warning,
+                "The night parade has gathered here. Nurarihyon awaits.",
+            );
+        }
+        true
+    }
+
+    /// Takes the up-stairs, arriving on the down-stairs of the floor above
+    ///
+    /// On the first floor they lead out of the dungeon, which wins the run if
+    /// the player carries the artifact. Returns `true` if the player climbed,
+    /// using up their turn.
+    pub fn try_ascend(&mut self) -> bool {
+        if self.dungeon.get_tile(self.player_pos()) != Some(TileType::StairsUp) {
+            self.message(MessageKind::Warning, "There are no stairs up here.");
+            return false;
+        }
+        if self.dungeon.depth <= 1 {
+            return self.try_leave_dungeon();
+        }
+
+        let new_depth = self.dungeon.depth - 1;
+        self.player_mut().energy.spend(ActionKind::Descend.cost());
+        self.change_floor(new_depth);
+        self.set_player_pos(self.dungeon.stairs_position);
+        self.update_fov();
+        self.message(
+            MessageKind::Info,
+            format!("You climb back up to depth {}.", new_depth),
+        );
+        true
+    }
+
+    /// Climbs out of the dungeon, but only with the artifact in hand
+    fn try_leave_dungeon(&mut self) -> bool {
+        if !self.inventory().contains(ItemKind::HyakkiEmaki) {
+            self.message(
+                MessageKind::Warning,
+                "You cannot leave without the Hyakki Yagyō Emaki.",
+            );
+            return false;
+        }
+        self.message(
+            MessageKind::Good,
+            "You climb into the dawn with the emaki. The night parade is over!",
+        );
+        self.end_run(RunEnd::Victory);
+        true
+    }
+
+    /// Stores the current floor and switches to the one at `depth`
+    ///
+    /// Floors are generated on the first visit and restored as they were left
+    /// afterwards. Monsters on stored floors do not act.
+    fn change_floor(&mut self, depth: u32) {
+        let next = self
+            .levels
+            .remove(&depth)
+            .unwrap_or_else(|| Dungeon::new_seeded(depth, self.seed));
+        let previous = self.swap_floor(next);
+        self.levels.insert(previous.depth, previous);
+    }
+
+    /// Makes `next` the current floor, moving its monsters and items into
+    /// `entities`, and hands back the floor that was current with its own
+    /// monsters and items packed into its `population`
+    fn swap_floor(&mut self, mut next: Dungeon) -> Dungeon {
+        let arriving = std::mem::take(&mut next.population);
+        let mut previous = std::mem::replace(&mut self.dungeon, next);
+        previous.population = self.entities.split_off(self.player_id);
+        self.entities.append(arriving);
+        previous
+    }
+
+    /// Where the player stands
+    pub fn player_pos(&self) -> Position {
+        *self
+            .entities
+            .position
+            .get(self.player_id)
+            .expect("The player always has a position")
+    }
+
+    /// Puts the player on `pos`; the caller recomputes the FOV
+    pub fn set_player_pos(&mut self, pos: Position) {
+        self.entities.position.insert(self.player_id, pos);
+    }
+
+    /// The entity to draw at `pos`, if the player can see that tile
+    pub fn visible_entity_at(&self, pos: Position) -> Option<&Renderable> {
+        self.entities
+            .renderable_at(pos)
+            .filter(|_| self.dungeon.is_visible(pos))
+    }
+
+    pub fn player(&self) -> &Player {
+        self.entities
+            .player
+            .get(self.player_id)
+            .expect("The player entity is always a player")
+    }
+
+    pub fn player_mut(&mut self) -> &mut Player {
+        self.entities
+            .player
+            .get_mut(self.player_id)
+            .expect("The player entity is always a player")
+    }
+
+    pub fn player_health(&self) -> &Health {
+        self.entities
+            .health
+            .get(self.player_id)
+            .expect("The player always has health")
+    }
+
+    pub fn player_health_mut(&mut self) -> &mut Health {
+        self.entities
+            .health
+            .get_mut(self.player_id)
+            .expect("The player always has health")
+    }
+
+    pub fn player_stats(&self) -> &Stats {
+        self.entities
+            .stats
+            .get(self.player_id)
+            .expect("The player always has stats")
+    }
+
+    /// The player component and stats together, for changes that touch both
+    pub(crate) fn player_and_stats_mut(&mut self) -> (&mut Player, &mut Stats) {
+        let player = self
+            .entities
+            .player
+            .get_mut(self.player_id)
+            .expect("The player entity is always a player");
+        let stats = self
+            .entities
+            .stats
+            .get_mut(self.player_id)
+            .expect("The player always has stats");
+        (player, stats)
+    }
+
+    pub fn inventory(&self) -> &Inventory {
+        self.entities
+            .inventory
+            .get(self.player_id)
+            .expect("The player always has an inventory")
+    }
+
+    pub fn inventory_mut(&mut self) -> &mut Inventory {
+        self.entities
+            .inventory
+            .get_mut(self.player_id)
+            .expect("The player always has an inventory")
+    }
+
+    /// What kind of yokai `monster` is
+    fn monster_kind(&self, monster: Entity) -> MonsterKind {
+        self.entities
+            .monster
+            .get(monster)
+            .expect("Only monsters are fought")
+            .kind
+    }
+
+    /// Whether an actor can step onto `pos` (walkable and not occupied by a monster)
+    pub fn is_blocked(&self, pos: Position) -> bool {
+        !self.dungeon.is_walkable(pos) || self.entities.monster_at(pos).is_some()
+    }
+
+    /// Picks a random walkable tile on the current floor that no monster is
+    /// standing on
+    pub fn random_open_position<R: Rng>(&self, rng: &mut R) -> Option<Position> {
+        let (width, height) = (self.dungeon.width, self.dungeon.height);
+        let open: Vec<Position> = (0..height)
+            .flat_map(|y| {
+                (0..width).map(move |x| Position {
+                    x: x as i32,
+                    y: y as i32,
+                })
+            })
+            .filter(|&pos| !self.is_blocked(pos))
+            .collect();
+        open.choose(rng).copied()
+    }
+
+    /// Recomputes what the player can see from their current position
+    fn update_fov(&mut self) {
+        self.dungeon.update_fov(self.player_pos());
+    }
+
+    /// Changes the tile at `pos` on the current floor
+    fn set_tile(&mut self, pos: Position, tile: TileType) {
+        self.dungeon.tiles[pos.y as usize][pos.x as usize] = tile;
+    }
+}
+
+/// The player and the monsters on the current floor, who take turns; borrowed
+/// apart from the rest of the game so the scheduler can be run in place
+struct TurnTakers<'a> {
+    entities: &'a mut Entities,
+    player_id: Entity,
+}
+
+impl Actors for TurnTakers<'_> {
+    fn monsters(&self) -> Vec<Entity> {
+        self.entities.monsters()
+    }
+
+    fn energy_mut(&mut self, actor: Actor) -> &mut Energy {
+        let energy = match actor {
+            Actor::Player => self
+                .entities
+                .player
+                .get_mut(self.player_id)
+                .map(|player| &mut player.energy),
+            Actor::Monster(monster) => self
+                .entities
+                .monster
+                .get_mut(monster)
+                .map(|monster| &mut monster.energy),
+        };
+        energy.expect("Everyone taking turns has energy")
+    }
+
+    fn speed(&self, actor: Actor) -> i32 {
+        match actor {
+            Actor::Player => self
+                .entities
+                .player
+                .get(self.player_id)
+                .expect("The player entity is always a player")
+                .speed(),
+            Actor::Monster(monster) => self
+                .entities
+                .monster
+                .get(monster)
+                .expect("Only monsters take turns")
+                .kind
+                .speed(),
         }
     }
 }
@@ -62,25 +691,54 @@ impl Default for Game {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entity::position::Position;
+    use crate::{
+        entity::{
+            monster::{AiState, Monster, MonsterKind},
+            position::Position,
+        },
+        spell::Spell,
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Removes every monster from the current floor
+    fn clear_monsters(game: &mut Game) {
+        for monster in game.entities.monsters() {
+            game.entities.despawn(monster);
+        }
+    }
+
+    /// Kind and position of every monster on the current floor, which stay
+    /// the same however the entities are renumbered
+    fn monster_layout(game: &Game) -> Vec<(MonsterKind, Position)> {
+        game.entities
+            .monsters()
+            .into_iter()
+            .map(|monster| {
+                (
+                    game.monster_kind(monster),
+                    game.entities.position.get(monster).copied().unwrap(),
+                )
+            })
+            .collect()
+    }
 
     #[test]
     fn test_player_starts_at_dungeon_player_start() {
         let game = Game::new();
-        assert_eq!(game.player.position, game.dungeon.player_start);
+        assert_eq!(game.player_pos(), game.dungeon.player_start);
     }
 
     #[test]
     fn test_player_movement_valid() {
         let mut game = Game::new();
-        let start_pos = game.player.position;
+        let start_pos = game.player_pos();
 
         // Try moving in each direction until we find a walkable tile
         for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
             let new_pos = start_pos.translate(dx, dy);
             if game.dungeon.is_walkable(new_pos) {
                 game.try_move_player(dx, dy);
-                assert_eq!(game.player.position, new_pos);
+                assert_eq!(game.player_pos(), new_pos);
                 return;
             }
         }
@@ -93,7 +751,7 @@ mod tests {
         let mut game = Game::new();
         // Move player to a wall position (0,0 is always a wall)
         let wall_adjacent = Position { x: 1, y: 1 };
-        game.player.position = wall_adjacent;
+        game.set_player_pos(wall_adjacent);
 
         // The corner (0,0) is always a wall
         let wall_pos = Position { x: 0, y: 0 };
@@ -102,17 +760,17 @@ mod tests {
         // Try to move into the wall (left)
         game.try_move_player(-1, 0);
         // Position should not change
-        assert_eq!(game.player.position, wall_adjacent);
+        assert_eq!(game.player_pos(), wall_adjacent);
     }
 
     #[test]
     fn test_player_cannot_move_outside_dungeon() {
         let mut game = Game::new();
-        game.player.position = Position { x: 0, y: 0 };
+        game.set_player_pos(Position { x: 0, y: 0 });
 
         // Try to move outside boundary
         game.try_move_player(-1, 0);
-        assert_eq!(game.player.position, Position { x: 0, y: 0 });
+        assert_eq!(game.player_pos(), Position { x: 0, y: 0 });
     }
 
     #[test]
@@ -121,7 +779,7 @@ mod tests {
         let initial_depth = game.dungeon.depth;
 
         // Move player to stairs
-        game.player.position = game.dungeon.stairs_position;
+        game.set_player_pos(game.dungeon.stairs_position);
 
         // Descend
         game.try_descend();
@@ -129,7 +787,7 @@ mod tests {
         // Depth should increase
         assert_eq!(game.dungeon.depth, initial_depth + 1);
         // Player should be at new dungeon's start position
-        assert_eq!(game.player.position, game.dungeon.player_start);
+        assert_eq!(game.player_pos(), game.dungeon.player_start);
     }
 
     #[test]
@@ -138,7 +796,7 @@ mod tests {
         let initial_depth = game.dungeon.depth;
 
         // Player is at start position (not on stairs)
-        let start_pos = game.player.position;
+        let start_pos = game.player_pos();
 
         // Try to descend (should fail)
         game.try_descend();
@@ -146,15 +804,388 @@ mod tests {
         // Depth should not change
         assert_eq!(game.dungeon.depth, initial_depth);
         // Position should not change
-        assert_eq!(game.player.position, start_pos);
+        assert_eq!(game.player_pos(), start_pos);
+    }
+
+    /// Game on the fixed test floor, with the player on its start and able to
+    /// see the room; shared by the tests of every part of the game
+    ///
+    /// The run seed is fixed too, so the floors below are the same every time.
+    pub(crate) fn fixed_game() -> Game {
+        let mut game = Game::with_seed(7);
+        game.swap_floor(Dungeon::new_fixed());
+        game.set_player_pos(game.dungeon.player_start);
+        game.update_fov();
+        game
+    }
+
+    /// Fixed-room game with a single monster placed next to the player (to the east)
+    fn game_with_adjacent_monster(kind: MonsterKind) -> (Game, Entity) {
+        let mut game = fixed_game();
+        let monster_pos = game.player_pos().translate(1, 0);
+        let monster = Monster::spawn(&mut game.entities, kind, monster_pos);
+        (game, monster)
+    }
+
+    #[test]
+    fn test_bump_attacks_instead_of_moving() {
+        let (mut game, _) = game_with_adjacent_monster(MonsterKind::Oni);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.player_pos(), game.dungeon.player_start);
+    }
+
+    #[test]
+    fn test_killing_monster_removes_it() {
+        let (mut game, _) = game_with_adjacent_monster(MonsterKind::Kappa);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..50 {
+            if game.entities.monsters().is_empty() {
+                break;
+            }
+            game.try_move_player_with_rng(1, 0, &mut rng);
+        }
+
+        assert!(game.entities.monsters().is_empty());
+        assert_eq!(game.kills, 1);
+    }
+
+    #[test]
+    fn test_end_turn_regenerates_mana() {
+        let mut game = Game::new();
+        clear_monsters(&mut game);
+        game.player_mut().spellbook.mana = 0;
+        for _ in 0..MANA_REGEN_INTERVAL {
+            game.end_turn();
+        }
+        assert_eq!(game.turn, MANA_REGEN_INTERVAL);
+        assert_eq!(game.player().spellbook.mana, 1);
+    }
+
+    #[test]
+    fn test_adjacent_monster_damages_player() {
+        let (mut game, _) = game_with_adjacent_monster(MonsterKind::Oni);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..20 {
+            game.end_turn_with_rng(&mut rng);
+        }
+
+        assert!(game.player_health().hp < game.player_health().max_hp);
+    }
+
+    #[test]
+    fn test_player_death_ends_game() {
+        let (mut game, _) = game_with_adjacent_monster(MonsterKind::Oni);
+        game.player_health_mut().hp = 1;
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..50 {
+            if game.is_game_over() {
+                break;
+            }
+            game.end_turn_with_rng(&mut rng);
+        }
+
+        assert!(game.player_health().is_dead());
+        assert_eq!(game.state, GameState::GameOver);
+        assert_eq!(game.end_cause(), "Killed by an Oni");
+    }
+
+    #[test]
+    fn test_fast_monster_acts_twice_per_turn() {
+        let (mut game, tengu) = game_with_adjacent_monster(MonsterKind::Tengu);
+        game.player_health_mut().hp = 1000;
+        game.entities.ai.insert(tengu, AiState::Chase);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.end_turn_with_rng(&mut rng);
+
+        let attacks = game
+            .messages
+            .iter()
+            .filter(|m| m.text.starts_with("The Tengu"))
+            .count();
+        assert_eq!(game.turn, 1);
+        assert_eq!(attacks, 2);
+    }
+
+    #[test]
+    fn test_haste_gives_two_actions_per_turn() {
+        let mut game = Game::with_seed(7);
+        clear_monsters(&mut game);
+        game.player_mut().status.add(StatusKind::Hasted, 10);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.end_turn_with_rng(&mut rng);
+        assert_eq!(game.turn, 0);
+        game.end_turn_with_rng(&mut rng);
+        assert_eq!(game.turn, 1);
+    }
+
+    #[test]
+    fn test_slow_takes_two_turns_per_action() {
+        let mut game = Game::with_seed(7);
+        clear_monsters(&mut game);
+        game.player_mut().status.add(StatusKind::Slowed, 10);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.end_turn_with_rng(&mut rng);
+        assert_eq!(game.turn, 2);
+    }
+
+    #[test]
+    fn test_monsters_take_turns() {
+        let mut game = fixed_game();
+        let kappa = Monster::spawn(
+            &mut game.entities,
+            MonsterKind::Kappa,
+            Position { x: 36, y: 25 },
+        );
+        let mut rng = StdRng::seed_from_u64(7);
+
+        game.end_turn_with_rng(&mut rng);
+
+        assert_eq!(game.entities.ai.get(kappa), Some(&AiState::Chase));
+        assert_eq!(
+            game.entities.position.get(kappa),
+            Some(&Position { x: 37, y: 25 })
+        );
+    }
+
+    #[test]
+    fn test_player_start_is_visible() {
+        let game = Game::new();
+        assert!(game.dungeon.is_visible(game.player_pos()));
+    }
+
+    #[test]
+    fn test_player_is_drawn_wherever_they_move() {
+        let mut game = fixed_game();
+        let mut rng = StdRng::seed_from_u64(1);
+        let drawn = |game: &Game| game.visible_entity_at(game.player_pos()).map(|e| e.glyph);
+        assert_eq!(drawn(&game), Some('@'));
+
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(drawn(&game), Some('@'));
+
+        let target = game.player_pos().translate(-3, 1);
+        game.targeting = Some(Targeting {
+            spell: Spell::Blink,
+            cursor: target,
+        });
+        assert!(game.cast_at_target());
+        assert_eq!(game.player_pos(), target);
+        assert_eq!(drawn(&game), Some('@'));
+
+        game.teleport_player(&mut rng);
+        assert_eq!(drawn(&game), Some('@'));
+
+        game.fall_to_next_depth("Fell", &mut rng);
+        assert_eq!(game.dungeon.depth, 2);
+        assert_eq!(drawn(&game), Some('@'));
+    }
+
+    #[test]
+    fn test_occupied_tile_is_blocked() {
+        let mut game = fixed_game();
+        let pos = Position { x: 40, y: 22 };
+        assert!(!game.is_blocked(pos));
+        let kappa = Monster::spawn(&mut game.entities, MonsterKind::Kappa, pos);
+        assert!(game.is_blocked(pos));
+        assert_eq!(game.entities.monster_at(pos), Some(kappa));
+    }
+
+    #[test]
+    fn test_fov_follows_player_movement() {
+        let mut game = Game::new();
+        game.swap_floor(Dungeon::new_fixed());
+        game.set_player_pos(Position { x: 35, y: 20 });
+        game.update_fov();
+        let far_corner = Position { x: 44, y: 29 };
+        assert!(!game.dungeon.is_visible(far_corner));
+
+        // Walk diagonally toward the far corner until it comes into view
+        for _ in 0..5 {
+            game.try_move_player(1, 0);
+            game.try_move_player(0, 1);
+        }
+        assert!(game.dungeon.is_visible(far_corner));
+    }
+
+    #[test]
+    fn test_descend_recomputes_fov() {
+        let mut game = Game::new();
+        game.set_player_pos(game.dungeon.stairs_position);
+        game.try_descend();
+        assert!(game.dungeon.is_visible(game.player_pos()));
+    }
+
+    #[test]
+    fn test_ascend_returns_to_same_floor() {
+        let mut game = Game::new();
+        let first_tiles = game.dungeon.tiles.clone();
+        let first_monsters = monster_layout(&game);
+        let stairs = game.dungeon.stairs_position;
+        game.set_player_pos(stairs);
+        game.try_descend();
+
+        // Arrive on the up-stairs of floor 2
+        assert_eq!(
+            game.dungeon.get_tile(game.player_pos()),
+            Some(TileType::StairsUp)
+        );
+        game.try_ascend();
+
+        assert_eq!(game.dungeon.depth, 1);
+        assert_eq!(game.player_pos(), stairs);
+        assert_eq!(game.dungeon.tiles, first_tiles);
+        assert_eq!(monster_layout(&game), first_monsters);
+        assert!(game.dungeon.is_visible(stairs));
+    }
+
+    #[test]
+    fn test_revisited_floor_keeps_changes() {
+        let mut game = Game::new();
+        game.set_player_pos(game.dungeon.stairs_position);
+        game.try_descend();
+        clear_monsters(&mut game);
+        game.dungeon.explored[0][0] = true;
+
+        game.try_ascend();
+        game.try_descend();
+
+        assert_eq!(game.dungeon.depth, 2);
+        assert!(game.entities.monsters().is_empty());
+        assert!(game.dungeon.is_explored(Position { x: 0, y: 0 }));
+        assert_eq!(game.levels.keys().collect::<Vec<_>>(), vec![&1]);
+    }
+
+    #[test]
+    fn test_failed_descend_is_reported() {
+        let mut game = Game::new();
+        game.try_descend();
+
+        let last = game.messages.last().unwrap();
+        assert_eq!(last.kind, MessageKind::Warning);
+        assert!(last.text.contains("no stairs"));
+    }
+
+    #[test]
+    fn test_kill_is_reported() {
+        let (mut game, kappa) = game_with_adjacent_monster(MonsterKind::Kappa);
+        game.entities.health.get_mut(kappa).unwrap().hp = 1;
+        game.damage_monster(kappa, 5);
+
+        let last = game.messages.last().unwrap();
+        assert_eq!(last.kind, MessageKind::Good);
+        assert_eq!(last.text, "You kill the Kappa!");
+    }
+
+    #[test]
+    fn test_kill_grants_xp_and_gold() {
+        let (mut game, oni) = game_with_adjacent_monster(MonsterKind::Oni);
+        game.entities.health.get_mut(oni).unwrap().hp = 1;
+        game.damage_monster(oni, 5);
+
+        assert_eq!(game.player().gold, MonsterKind::Oni.gold());
+        assert_eq!(game.player().level, 2);
+        assert!(game.messages.iter().any(|m| m.text == "You reach level 2!"));
+    }
+
+    #[test]
+    fn test_regeneration_heals_each_turn() {
+        let mut game = Game::new();
+        clear_monsters(&mut game);
+        game.player_health_mut().hp = 10;
+        game.player_mut().status.add(StatusKind::Regenerating, 2);
+
+        game.end_turn();
+        assert_eq!(game.player_health().hp, 11);
+        game.end_turn();
+        assert_eq!(game.player_health().hp, 12);
+        assert!(!game.player().status.has(StatusKind::Regenerating));
+        game.end_turn();
+        assert_eq!(game.player_health().hp, 12);
+    }
+
+    #[test]
+    fn test_ascend_not_on_stairs() {
+        let mut game = Game::new();
+        game.set_player_pos(game.dungeon.stairs_position);
+        game.try_descend();
+        game.set_player_pos(game.player_pos().translate(1, 0));
+
+        game.try_ascend();
+        assert_eq!(game.dungeon.depth, 2);
+    }
+
+    #[test]
+    fn test_boss_drops_artifact() {
+        let (mut game, boss) = game_with_adjacent_monster(MonsterKind::Nurarihyon);
+        let position = *game.entities.position.get(boss).unwrap();
+        game.entities.health.get_mut(boss).unwrap().hp = 1;
+        game.damage_monster(boss, 5);
+
+        let dropped = game.entities.item_at(position);
+        assert_eq!(
+            dropped
+                .and_then(|item| game.entities.item.get(item))
+                .map(|item| item.kind),
+            Some(ItemKind::HyakkiEmaki)
+        );
+    }
+
+    #[test]
+    fn test_leaving_requires_artifact() {
+        let mut game = Game::new();
+        game.try_ascend();
+        assert!(!game.is_game_over());
+        assert!(game.messages.last().unwrap().text.contains("cannot leave"));
+
+        game.inventory_mut()
+            .add(Item::new(ItemKind::HyakkiEmaki))
+            .unwrap();
+        game.try_ascend();
+        assert!(game.is_game_over());
+        assert!(game.has_won());
+    }
+
+    #[test]
+    fn test_boss_awaits_on_deepest_floor() {
+        let mut game = Game::new();
+        game.swap_floor(Dungeon::new_random(MAX_DEPTH - 1));
+        game.set_player_pos(game.dungeon.stairs_position);
+        game.try_descend();
+
+        assert_eq!(game.dungeon.depth, MAX_DEPTH);
+        assert!(monster_layout(&game).iter().any(|(kind, _)| kind.is_boss()));
+        assert!(game.messages.last().unwrap().text.contains("Nurarihyon"));
+    }
+
+    #[test]
+    fn test_same_seed_same_floors() {
+        let mut a = Game::with_seed(42);
+        let mut b = Game::with_seed(42);
+        assert_eq!(a.dungeon.tiles, b.dungeon.tiles);
+
+        for game in [&mut a, &mut b] {
+            game.set_player_pos(game.dungeon.stairs_position);
+            game.try_descend();
+        }
+        assert_eq!(a.dungeon.depth, 2);
+        assert_eq!(a.dungeon.tiles, b.dungeon.tiles);
+        assert_eq!(monster_layout(&a), monster_layout(&b));
     }
 
     #[test]
     fn test_cannot_descend_past_max_depth() {
         let mut game = Game::new();
         // Set dungeon to max depth
-        game.dungeon = Dungeon::new_random(MAX_DEPTH);
-        game.player.position = game.dungeon.stairs_position;
+        game.swap_floor(Dungeon::new_random(MAX_DEPTH));
+        game.set_player_pos(game.dungeon.stairs_position);
 
         // Try to descend (should fail since we're at max depth)
         game.try_descend();
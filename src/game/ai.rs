@@ -0,0 +1,336 @@
+use crate::{
+    ecs::{Entities, Entity},
+    entity::{monster::AiState, position::Position},
+    world::{
+        dungeon::Dungeon,
+        tile::{Movement, TileType},
+    },
+};
+use rand::{Rng, seq::SliceRandom};
+
+/// Monsters move in the same four directions as the player
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// What a monster did with its turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonsterAction {
+    Wait,
+    Moved,
+    /// The monster opened a closed door instead of moving
+    OpenedDoor,
+    /// The monster is adjacent to the player and attacks; the caller resolves combat
+    AttackPlayer,
+}
+
+/// Runs a single turn for `monster`, one of the `entities` on `dungeon`
+///
+/// The monster first re-evaluates its `AiState`, then moves according to it.
+/// A chasing monster next to the player attacks instead of moving, and one
+/// walking into a closed door spends its turn opening it. Monsters keep off
+/// terrain that would hurt them, so only tengu cross lava and chasms and
+/// only kappa and tengu take to deep water.
+///
+/// # Why does "the monster sees the player" use the player's FOV?
+///
+/// Field of view is symmetric (see `world::fov`), so the player can see the
+/// monster's tile exactly when the monster can see the player. Reusing the
+/// player's visibility grid avoids computing a FOV per monster every turn.
+pub fn take_turn<R: Rng>(
+    dungeon: &mut Dungeon,
+    entities: &mut Entities,
+    monster: Entity,
+    player_pos: Position,
+    rng: &mut R,
+) -> MonsterAction {
+    let state = next_state(dungeon, entities, monster);
+    entities.ai.insert(monster, state);
+
+    let from = *entities
+        .position
+        .get(monster)
+        .expect("Monsters have a position");
+    let movement = entities
+        .monster
+        .get(monster)
+        .expect("Only monsters take turns")
+        .kind
+        .movement();
+    if state == AiState::Chase && from.distance_squared(player_pos) == 1 {
+        return MonsterAction::AttackPlayer;
+    }
+
+    let step = match state {
+        AiState::Idle => None,
+        AiState::Wander => random_step(dungeon, entities, from, player_pos, movement, rng),
+        AiState::Chase => step_toward(dungeon, entities, from, player_pos, movement),
+        AiState::Flee => step_away(dungeon, entities, from, player_pos, movement),
+    };
+
+    match step {
+        Some(to) if dungeon.get_tile(to) == Some(TileType::DoorClosed) => {
+            dungeon.tiles[to.y as usize][to.x as usize] = TileType::DoorOpen;
+            MonsterAction::OpenedDoor
+        }
+        Some(to) => {
+            entities.position.insert(monster, to);
+            MonsterAction::Moved
+        }
+        None => MonsterAction::Wait,
+    }
+}
+
+/// Decides the monster's next AI state
+///
+/// ```text
+///            sees player             HP low & sees player
+///   Idle ─────────────────▶ Chase ───────────────────────▶ Flee
+///                             │                              │
+///                             │ loses sight                  │ loses sight
+///                             ▼                              ▼
+///                           Wander ◀─────────────────────────┘
+/// ```
+fn next_state(dungeon: &Dungeon, entities: &Entities, monster: Entity) -> AiState {
+    let sees_player = entities
+        .position
+        .get(monster)
+        .is_some_and(|&pos| dungeon.is_visible(pos));
+    let badly_hurt = match (entities.monster.get(monster), entities.health.get(monster)) {
+        (Some(monster), Some(health)) => monster.is_badly_hurt(health),
+        _ => false,
+    };
+    let ai = entities.ai.get(monster).copied().unwrap_or(AiState::Idle);
+
+    match (sees_player, badly_hurt, ai) {
+        (true, true, _) => AiState::Flee,
+        (true, false, _) => AiState::Chase,
+        (false, _, AiState::Idle) => AiState::Idle,
+        (false, _, _) => AiState::Wander,
+    }
+}
+
+/// Neighbouring tiles a monster moving by `movement` could safely step onto,
+/// counting closed doors it can open but not locked ones
+fn open_neighbours(
+    dungeon: &Dungeon,
+    entities: &Entities,
+    from: Position,
+    player_pos: Position,
+    movement: Movement,
+) -> Vec<Position> {
+    DIRECTIONS
+        .iter()
+        .map(|&(dx, dy)| from.translate(dx, dy))
+        .filter(|&pos| pos != player_pos && entities.monster_at(pos).is_none())
+        .filter(|&pos| match dungeon.get_tile(pos) {
+            Some(TileType::DoorClosed) => true,
+            Some(tile) => tile.move_cost(movement).is_some() && !tile.is_hazardous_for(movement),
+            None => false,
+        })
+        .collect()
+}
+
+fn random_step<R: Rng>(
+    dungeon: &Dungeon,
+    entities: &Entities,
+    from: Position,
+    player_pos: Position,
+    movement: Movement,
+    rng: &mut R,
+) -> Option<Position> {
+    open_neighbours(dungeon, entities, from, player_pos, movement)
+        .choose(rng)
+        .copied()
+}
+
+/// Greedy step that strictly reduces the distance to `target`
+fn step_toward(
+    dungeon: &Dungeon,
+    entities: &Entities,
+    from: Position,
+    target: Position,
+    movement: Movement,
+) -> Option<Position> {
+    let current = from.distance_squared(target);
+    open_neighbours(dungeon, entities, from, target, movement)
+        .into_iter()
+        .filter(|&pos| pos.distance_squared(target) < current)
+        .min_by_key(|&pos| pos.distance_squared(target))
+}
+
+/// Greedy step that strictly increases the distance to `threat`
+fn step_away(
+    dungeon: &Dungeon,
+    entities: &Entities,
+    from: Position,
+    threat: Position,
+    movement: Movement,
+) -> Option<Position> {
+    let current = from.distance_squared(threat);
+    open_neighbours(dungeon, entities, from, threat, movement)
+        .into_iter()
+        .filter(|&pos| pos.distance_squared(threat) > current)
+        .max_by_key(|&pos| pos.distance_squared(threat))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::monster::{Monster, MonsterKind};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Fixed room with the player at its center and one kappa at `monster_pos`
+    fn setup(monster_pos: Position) -> (Dungeon, Entities, Entity, Position) {
+        setup_kind(MonsterKind::Kappa, monster_pos)
+    }
+
+    fn setup_kind(
+        kind: MonsterKind,
+        monster_pos: Position,
+    ) -> (Dungeon, Entities, Entity, Position) {
+        let mut dungeon = Dungeon::new_fixed();
+        let mut entities = Entities::default();
+        let player_pos = dungeon.player_start;
+        let monster = Monster::spawn(&mut entities, kind, monster_pos);
+        dungeon.update_fov(player_pos);
+        (dungeon, entities, monster, player_pos)
+    }
+
+    fn position(entities: &Entities, monster: Entity) -> Position {
+        *entities.position.get(monster).unwrap()
+    }
+
+    fn ai(entities: &Entities, monster: Entity) -> AiState {
+        *entities.ai.get(monster).unwrap()
+    }
+
+    #[test]
+    fn test_monster_chases_visible_player() {
+        let (mut dungeon, mut entities, monster, player_pos) = setup(Position { x: 36, y: 25 });
+        let before = position(&entities, monster).distance_squared(player_pos);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+        assert_eq!(ai(&entities, monster), AiState::Chase);
+        let after = position(&entities, monster).distance_squared(player_pos);
+        assert!(after < before);
+    }
+
+    #[test]
+    fn test_adjacent_chasing_monster_attacks() {
+        let (mut dungeon, mut entities, monster, player_pos) = setup(Position { x: 39, y: 25 });
+        assert_eq!(player_pos, Position { x: 40, y: 25 });
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let action = take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+        assert_eq!(action, MonsterAction::AttackPlayer);
+        assert_eq!(position(&entities, monster), Position { x: 39, y: 25 });
+    }
+
+    #[test]
+    fn test_hurt_monster_flees() {
+        let (mut dungeon, mut entities, monster, player_pos) = setup(Position { x: 38, y: 25 });
+        entities.health.get_mut(monster).unwrap().hp = 1;
+        let before = position(&entities, monster).distance_squared(player_pos);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+        assert_eq!(ai(&entities, monster), AiState::Flee);
+        let after = position(&entities, monster).distance_squared(player_pos);
+        assert!(after > before);
+    }
+
+    #[test]
+    fn test_idle_monster_out_of_sight_stays_put() {
+        let (mut dungeon, mut entities, monster, _) = setup(Position { x: 36, y: 21 });
+        // Player far away, outside the room, so the monster can't see them
+        let player_pos = Position { x: 5, y: 5 };
+        dungeon.update_fov(player_pos);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+        assert_eq!(ai(&entities, monster), AiState::Idle);
+        assert_eq!(position(&entities, monster), Position { x: 36, y: 21 });
+    }
+
+    #[test]
+    fn test_monster_that_loses_sight_wanders() {
+        let (mut dungeon, mut entities, monster, _) = setup(Position { x: 36, y: 21 });
+        entities.ai.insert(monster, AiState::Chase);
+        let player_pos = Position { x: 5, y: 5 };
+        dungeon.update_fov(player_pos);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+        assert_eq!(ai(&entities, monster), AiState::Wander);
+        let pos = position(&entities, monster);
+        assert!(dungeon.is_walkable(pos));
+        assert_eq!(pos.distance_squared(Position { x: 36, y: 21 }), 1);
+    }
+
+    #[test]
+    fn test_monsters_do_not_stack() {
+        let (mut dungeon, mut entities, monster, player_pos) = setup(Position { x: 38, y: 25 });
+        // A second monster standing directly between the first and the player
+        let other = Monster::spawn(&mut entities, MonsterKind::Kappa, Position { x: 39, y: 25 });
+        let mut rng = StdRng::seed_from_u64(1);
+
+        take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+        assert_ne!(position(&entities, monster), position(&entities, other));
+    }
+
+    #[test]
+    fn test_monster_opens_closed_door() {
+        let monster_pos = Position { x: 36, y: 21 };
+        let (mut dungeon, mut entities, monster, _) = setup(monster_pos);
+        let door = monster_pos.translate(1, 0);
+        dungeon.tiles[door.y as usize][door.x as usize] = TileType::DoorClosed;
+        // Box the monster in so the door is its only way out
+        for wall in [(0, -1), (0, 1), (-1, 0)] {
+            let wall = monster_pos.translate(wall.0, wall.1);
+            dungeon.tiles[wall.y as usize][wall.x as usize] = TileType::Wall;
+        }
+        entities.ai.insert(monster, AiState::Wander);
+        let player_pos = Position { x: 5, y: 5 };
+        dungeon.update_fov(player_pos);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let action = take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+        assert_eq!(action, MonsterAction::OpenedDoor);
+        assert_eq!(dungeon.get_tile(door), Some(TileType::DoorOpen));
+        assert_eq!(position(&entities, monster), monster_pos);
+
+        dungeon.tiles[door.y as usize][door.x as usize] = TileType::DoorLocked;
+        let action = take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+        assert_eq!(action, MonsterAction::Wait);
+    }
+
+    #[test]
+    fn test_monsters_keep_off_hazards_they_cannot_cross() {
+        let monster_pos = Position { x: 36, y: 21 };
+        let lava = monster_pos.translate(1, 0);
+        let player_pos = Position { x: 5, y: 5 };
+
+        for (kind, crosses) in [(MonsterKind::Kitsune, false), (MonsterKind::Tengu, true)] {
+            let (mut dungeon, mut entities, monster, _) = setup_kind(kind, monster_pos);
+            dungeon.tiles[lava.y as usize][lava.x as usize] = TileType::Lava;
+            for wall in [(0, -1), (0, 1), (-1, 0)] {
+                let wall = monster_pos.translate(wall.0, wall.1);
+                dungeon.tiles[wall.y as usize][wall.x as usize] = TileType::Wall;
+            }
+            entities.ai.insert(monster, AiState::Wander);
+            dungeon.update_fov(player_pos);
+            let mut rng = StdRng::seed_from_u64(1);
+
+            take_turn(&mut dungeon, &mut entities, monster, player_pos, &mut rng);
+
+            assert_eq!(position(&entities, monster) == lava, crosses, "{:?}", kind);
+        }
+    }
+}
@@ -7,6 +7,93 @@ pub const MIN_ROOMS: usize = 8;
 pub const MAX_ROOMS: usize = 12;
 pub const MIN_ROOM_SIZE: i32 = 6;
 pub const MAX_ROOM_SIZE: i32 = 10;
+/// Chance that a doorway into a room gets a door
+pub const DOOR_CHANCE: f64 = 0.6;
+/// Chance per floor that a dead-end room is locked, with its key elsewhere
+pub const LOCKED_ROOM_CHANCE: f64 = 0.3;
+/// Chance that a doorway is hidden behind a secret door instead
+pub const SECRET_DOOR_CHANCE: f64 = 0.15;
 
-/// Dungeon depth
+/// Player starting stats
+pub const PLAYER_MAX_HP: i32 = 30;
+pub const PLAYER_ATTACK: i32 = 5;
+pub const PLAYER_DEFENSE: i32 = 2;
+pub const PLAYER_MAX_MANA: i32 = 20;
+
+/// Experience needed per level: reaching level `n + 1` takes `n * XP_PER_LEVEL` XP
+pub const XP_PER_LEVEL: u32 = 20;
+/// Stat gains on each level up
+pub const LEVEL_UP_HP: i32 = 5;
+pub const LEVEL_UP_ATTACK: i32 = 1;
+
+/// Turns between each point of mana regeneration
+pub const MANA_REGEN_INTERVAL: u64 = 4;
+
+/// Number of item slots in the player's inventory (one per letter a-z)
+pub const INVENTORY_CAPACITY: usize = 26;
+
+/// Hazard terrain: chance per floor of a river, and of a lava lake or chasm
+/// from the depth where they start to appear
+pub const RIVER_CHANCE: f64 = 0.35;
+pub const LAVA_LAKE_MIN_DEPTH: u32 = 4;
+pub const LAVA_LAKE_CHANCE: f64 = 0.4;
+pub const CHASM_MIN_DEPTH: u32 = 2;
+pub const CHASM_CHANCE: f64 = 0.25;
+
+/// Prefab vaults: how many may be stamped into a floor, and the chance of each
+pub const MAX_VAULTS_PER_FLOOR: usize = 2;
+pub const VAULT_CHANCE: f64 = 0.5;
+
+/// Monster generation parameters
+pub const MAX_MONSTERS_PER_ROOM: usize = 2;
+
+/// Item generation parameters
+pub const MAX_ITEMS_PER_ROOM: usize = 1;
+
+/// Most traps hidden on a single floor
+pub const MAX_TRAPS_PER_FLOOR: usize = 6;
+
+/// Item effect parameters
+pub const HEALING_POTION_AMOUNT: i32 = 15;
+/// Turns of regeneration after drinking a healing potion
+pub const HEALING_POTION_REGEN_TURNS: u32 = 5;
+pub const OFUDA_DAMAGE: i32 = 8;
+pub const OFUDA_RADIUS: i32 = 3;
+
+/// Hazard terrain effects
+pub const LAVA_DAMAGE: i32 = 5;
+/// Turns the player keeps burning after touching lava, and the damage per turn
+pub const BURN_TURNS: u32 = 3;
+pub const BURN_DAMAGE: i32 = 2;
+/// Turns the player can swim in deep water before starting to drown
+pub const SWIM_TURNS: u32 = 5;
+pub const DROWN_DAMAGE: i32 = 4;
+pub const FALL_DAMAGE: i32 = 5;
+
+/// Trap effects
+pub const SPIKE_PIT_DAMAGE: i32 = 6;
+pub const DART_DAMAGE: i32 = 2;
+/// Turns a poison dart keeps hurting, and the damage per turn
+pub const POISON_TURNS: u32 = 6;
+pub const POISON_DAMAGE: i32 = 1;
+/// Chance to find each hidden trap next to the player when searching, and
+/// to notice it in passing on every step
+pub const SEARCH_CHANCE: f64 = 0.5;
+pub const PERCEPTION_CHANCE: f64 = 0.1;
+
+/// Dungeon depth; the deepest floor is the boss arena
 pub const MAX_DEPTH: u32 = 10;
+
+/// Score bonus for escaping the dungeon with the artifact
+pub const VICTORY_BONUS: u64 = 1000;
+
+/// Field of view radius (in tiles)
+pub const FOV_RADIUS: i32 = 8;
+
+/// Number of messages kept in the message log
+pub const MESSAGE_LOG_CAPACITY: usize = 200;
+
+/// Spell effect parameters
+pub const HEAL_SPELL_AMOUNT: i32 = 12;
+pub const HASTE_SPELL_TURNS: u32 = 10;
+pub const BLAST_RADIUS: i32 = 2;
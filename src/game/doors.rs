@@ -0,0 +1,205 @@
+use crate::{
+    entity::position::Position,
+    game::{Game, state::GameState},
+    item::ItemKind,
+    message::MessageKind,
+    world::tile::TileType,
+};
+
+/// Orthogonal neighbours, in the order doors next to the player are listed
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+impl Game {
+    /// Opens the closed door at `pos`
+    pub(crate) fn open_door(&mut self, pos: Position) {
+        self.set_tile(pos, TileType::DoorOpen);
+        self.message(MessageKind::Info, "You open the door.");
+        self.update_fov();
+    }
+
+    /// Opens the locked door at `pos` with a key from the inventory
+    ///
+    /// Returns `true` if the door was unlocked, which uses up the key and the
+    /// player's turn.
+    pub(crate) fn try_unlock_door(&mut self, pos: Position) -> bool {
+        let key = self
+            .inventory()
+            .items
+            .iter()
+            .position(|item| item.kind == ItemKind::Key);
+        let Some(index) = key else {
+            self.message(MessageKind::Warning, "The door is locked.");
+            return false;
+        };
+
+        self.inventory_mut().remove(index);
+        self.set_tile(pos, TileType::DoorOpen);
+        self.message(MessageKind::Good, "You unlock the door with your key.");
+        self.update_fov();
+        true
+    }
+
+    /// Closes the open door next to the player
+    ///
+    /// With several open doors around, asks which one to close by switching
+    /// to `GameState::CloseDoor`. Returns `true` if the player's turn was used.
+    pub fn try_close_door(&mut self) -> bool {
+        let doors = self.adjacent_open_doors();
+        match doors.as_slice() {
+            [] => {
+                self.message(MessageKind::Warning, "There is no open door next to you.");
+                false
+            }
+            [(dx, dy)] => self.close_door_toward(*dx, *dy),
+            _ => {
+                self.message(MessageKind::Info, "Close which door? (direction)");
+                self.state = GameState::CloseDoor;
+                false
+            }
+        }
+    }
+
+    /// Closes the door in direction (`dx`, `dy`) and goes back to the map
+    ///
+    /// Returns `true` if the door was closed.
+    pub fn close_door_toward(&mut self, dx: i32, dy: i32) -> bool {
+        self.state = GameState::Playing;
+        let pos = self.player_pos().translate(dx, dy);
+        if self.dungeon.get_tile(pos) != Some(TileType::DoorOpen) {
+            self.message(MessageKind::Warning, "There is no open door there.");
+            return false;
+        }
+        if self.entities.monster_at(pos).is_some() || self.entities.item_at(pos).is_some() {
+            self.message(MessageKind::Warning, "Something is in the way.");
+            return false;
+        }
+
+        self.set_tile(pos, TileType::DoorClosed);
+        self.message(MessageKind::Info, "You close the door.");
+        self.update_fov();
+        true
+    }
+
+    /// Leaves `GameState::CloseDoor` without closing anything
+    pub fn cancel_close_door(&mut self) {
+        self.state = GameState::Playing;
+    }
+
+    /// Directions of the open doors next to the player
+    fn adjacent_open_doors(&self) -> Vec<(i32, i32)> {
+        DIRECTIONS
+            .into_iter()
+            .filter(|&(dx, dy)| {
+                let pos = self.player_pos().translate(dx, dy);
+                self.dungeon.get_tile(pos) == Some(TileType::DoorOpen)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entity::{
+            monster::{Monster, MonsterKind},
+            position::Position,
+        },
+        game::{Game, state::GameState, tests::fixed_game},
+        item::{Item, ItemKind},
+        world::tile::TileType,
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Fixed-room game with a door of type `door` just east of the player
+    fn game_with_door(door: TileType) -> (Game, Position) {
+        let mut game = fixed_game();
+        let door_pos = game.player_pos().translate(1, 0);
+        game.dungeon.tiles[door_pos.y as usize][door_pos.x as usize] = door;
+        game.update_fov();
+        (game, door_pos)
+    }
+
+    #[test]
+    fn test_bumping_opens_door_without_moving() {
+        let (mut game, door) = game_with_door(TileType::DoorClosed);
+        let start = game.player_pos();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.dungeon.get_tile(door), Some(TileType::DoorOpen));
+        assert_eq!(game.player_pos(), start);
+
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.player_pos(), door);
+    }
+
+    #[test]
+    fn test_closed_door_blocks_sight() {
+        let (mut game, door) = game_with_door(TileType::DoorClosed);
+        let behind = door.translate(1, 0);
+
+        // Wall in the tile behind the door so it can only be seen through it
+        for wall in [
+            door.translate(0, -1),
+            door.translate(1, -1),
+            door.translate(0, 1),
+            door.translate(1, 1),
+            door.translate(2, 0),
+        ] {
+            game.dungeon.tiles[wall.y as usize][wall.x as usize] = TileType::Wall;
+        }
+        game.update_fov();
+        assert!(game.dungeon.is_visible(door));
+        assert!(!game.dungeon.is_visible(behind));
+
+        game.open_door(door);
+        assert!(game.dungeon.is_visible(behind));
+    }
+
+    #[test]
+    fn test_locked_door_needs_key() {
+        let (mut game, door) = game_with_door(TileType::DoorLocked);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(!game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.dungeon.get_tile(door), Some(TileType::DoorLocked));
+
+        game.inventory_mut().add(Item::new(ItemKind::Key)).unwrap();
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.dungeon.get_tile(door), Some(TileType::DoorOpen));
+        assert!(!game.inventory().contains(ItemKind::Key));
+    }
+
+    #[test]
+    fn test_close_single_adjacent_door() {
+        let (mut game, door) = game_with_door(TileType::DoorOpen);
+        assert!(game.try_close_door());
+        assert_eq!(game.dungeon.get_tile(door), Some(TileType::DoorClosed));
+
+        // Nothing left to close
+        assert!(!game.try_close_door());
+    }
+
+    #[test]
+    fn test_cannot_close_door_on_monster() {
+        let (mut game, door) = game_with_door(TileType::DoorOpen);
+        Monster::spawn(&mut game.entities, MonsterKind::Kappa, door);
+        assert!(!game.try_close_door());
+        assert_eq!(game.dungeon.get_tile(door), Some(TileType::DoorOpen));
+    }
+
+    #[test]
+    fn test_several_doors_ask_for_direction() {
+        let (mut game, east) = game_with_door(TileType::DoorOpen);
+        let west = game.player_pos().translate(-1, 0);
+        game.dungeon.tiles[west.y as usize][west.x as usize] = TileType::DoorOpen;
+
+        assert!(!game.try_close_door());
+        assert_eq!(game.state, GameState::CloseDoor);
+
+        assert!(game.close_door_toward(-1, 0));
+        assert_eq!(game.state, GameState::Playing);
+        assert_eq!(game.dungeon.get_tile(west), Some(TileType::DoorClosed));
+        assert_eq!(game.dungeon.get_tile(east), Some(TileType::DoorOpen));
+    }
+}
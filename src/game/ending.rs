@@ -0,0 +1,253 @@
+use crate::{
+    entity::position::Position,
+    game::{Game, config::VICTORY_BONUS, state::GameState},
+    item::Item,
+    score::ScoreEntry,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+
+/// Number of messages from the end of the log included in the morgue file
+const MORGUE_MESSAGES: usize = 20;
+
+/// How a run ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunEnd {
+    /// Killed by the monster with this name
+    Killed { by: String },
+    /// Killed by the dungeon itself; `cause` reads like "Drowned"
+    Perished { cause: String },
+    /// Escaped to the surface with the artifact
+    Victory,
+}
+
+impl RunEnd {
+    /// One-line description, e.g. "Killed by an Oni"
+    pub fn cause(&self) -> String {
+        match self {
+            RunEnd::Killed { by } => format!("Killed by {}", with_article(by)),
+            RunEnd::Perished { cause } => cause.clone(),
+            RunEnd::Victory => "Escaped with the Hyakki Yagyō Emaki".to_string(),
+        }
+    }
+
+    pub fn is_victory(&self) -> bool {
+        *self == RunEnd::Victory
+    }
+}
+
+/// `"Oni"` -> `"an Oni"`, `"Kappa"` -> `"a Kappa"`
+fn with_article(name: &str) -> String {
+    let vowel = name
+        .chars()
+        .next()
+        .is_some_and(|c| "AEIOUaeiou".contains(c));
+    format!("{} {}", if vowel { "an" } else { "a" }, name)
+}
+
+impl Game {
+    /// Ends the run and switches to the game over screen
+    pub(crate) fn end_run(&mut self, end: RunEnd) {
+        self.run_end = Some(end);
+        self.state = GameState::GameOver;
+    }
+
+    /// Deepest floor visited during the run
+    pub fn deepest_depth(&self) -> u32 {
+        let stored = self.levels.keys().copied().max().unwrap_or(0);
+        self.dungeon.depth.max(stored)
+    }
+
+    /// Final score of the run
+    ///
+    /// ```text
+    /// score = deepest depth x 100 + kills x 10 + level x 50 + gold
+    ///         (+ VICTORY_BONUS if the player escaped with the artifact)
+    /// ```
+    pub fn score(&self) -> u64 {
+        let bonus = if self.has_won() { VICTORY_BONUS } else { 0 };
+        self.deepest_depth() as u64 * 100
+            + self.kills as u64 * 10
+            + self.player().level as u64 * 50
+            + self.player().gold as u64
+            + bonus
+    }
+
+    /// Whether the run ended with the player escaping with the artifact
+    pub fn has_won(&self) -> bool {
+        self.run_end.as_ref().is_some_and(RunEnd::is_victory)
+    }
+
+    /// The run as it will appear in the high score table
+    pub fn score_entry(&self) -> ScoreEntry {
+        ScoreEntry {
+            score: self.score(),
+            depth: self.deepest_depth(),
+            turns: self.turn,
+            cause: self.end_cause(),
+            seed: self.seed,
+        }
+    }
+
+    /// How the run ended, or that it is still going
+    pub fn end_cause(&self) -> String {
+        self.run_end
+            .as_ref()
+            .map_or_else(|| "Still exploring".to_string(), RunEnd::cause)
+    }
+
+    /// A plain-text dump of the run for sharing: summary, character,
+    /// inventory, the final floor and the last messages
+    pub fn morgue(&self) -> String {
+        let mut out = String::new();
+        self.write_morgue(&mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    fn write_morgue(&self, out: &mut impl Write) -> fmt::Result {
+        let player = self.player();
+        let health = self.player_health();
+        let stats = self.player_stats();
+        let book = &player.spellbook;
+        let slot = |item: Option<Item>| item.map_or("(none)", |i| i.name());
+
+        writeln!(out, "Hyakki (百鬼) morgue file")?;
+        writeln!(out, "========================")?;
+        writeln!(out)?;
+        writeln!(out, "{}.", self.end_cause())?;
+        writeln!(
+            out,
+            "Final depth: {}  Deepest depth: {}",
+            self.dungeon.depth,
+            self.deepest_depth()
+        )?;
+        writeln!(out, "Turns: {}", self.turn)?;
+        writeln!(out, "Score: {}", self.score())?;
+        writeln!(out, "Seed: {}", self.seed)?;
+        writeln!(out)?;
+
+        writeln!(out, "-- Character --")?;
+        writeln!(
+            out,
+            "Level {} ({}/{} XP)",
+            player.level,
+            player.xp,
+            player.xp_to_next_level()
+        )?;
+        writeln!(
+            out,
+            "HP {}/{}  MP {}/{}",
+            health.hp, health.max_hp, book.mana, book.max_mana
+        )?;
+        writeln!(out, "Attack {}  Defense {}", stats.attack, stats.defense)?;
+        writeln!(out, "Kills {}  Gold {} mon", self.kills, player.gold)?;
+        writeln!(out, "Weapon: {}", slot(player.weapon))?;
+        writeln!(out, "Armour: {}", slot(player.armour))?;
+        writeln!(out)?;
+
+        writeln!(out, "-- Inventory --")?;
+        if self.inventory().is_empty() {
+            writeln!(out, "(empty)")?;
+        }
+        for (i, item) in self.inventory().items.iter().enumerate() {
+            writeln!(out, "{}) {}", (b'a' + i as u8) as char, item.name())?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "-- Final map (depth {}) --", self.dungeon.depth)?;
+        for line in self.map_lines() {
+            writeln!(out, "{}", line)?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "-- Last messages --")?;
+        let skip = self.messages.len().saturating_sub(MORGUE_MESSAGES);
+        for message in self.messages.iter().skip(skip) {
+            writeln!(out, "[{:>4}] {}", message.turn, message.text)?;
+        }
+        Ok(())
+    }
+
+    /// The current floor as the player knew it: explored tiles and the
+    /// entities in view
+    fn map_lines(&self) -> Vec<String> {
+        let dungeon = &self.dungeon;
+        (0..dungeon.height as i32)
+            .map(|y| {
+                let line: String = (0..dungeon.width as i32)
+                    .map(|x| {
+                        let pos = Position { x, y };
+                        if let Some(entity) = self.visible_entity_at(pos) {
+                            entity.glyph
+                        } else if dungeon.is_explored(pos) {
+                            dungeon.tiles[y as usize][x as usize].to_char()
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::tests::fixed_game, message::MessageKind};
+
+    #[test]
+    fn test_cause_uses_article() {
+        let oni = RunEnd::Killed {
+            by: "Oni".to_string(),
+        };
+        let kappa = RunEnd::Killed {
+            by: "Kappa".to_string(),
+        };
+        assert_eq!(oni.cause(), "Killed by an Oni");
+        assert_eq!(kappa.cause(), "Killed by a Kappa");
+    }
+
+    #[test]
+    fn test_end_run_enters_game_over() {
+        let mut game = Game::new();
+        game.end_run(RunEnd::Killed {
+            by: "Tengu".to_string(),
+        });
+        assert!(game.is_game_over());
+        assert_eq!(game.score_entry().cause, "Killed by a Tengu");
+    }
+
+    #[test]
+    fn test_score_counts_deepest_floor() {
+        let mut game = Game::new();
+        let base = game.score();
+        game.set_player_pos(game.dungeon.stairs_position);
+        game.try_descend();
+        game.set_player_pos(game.dungeon.player_start);
+        game.try_ascend();
+
+        assert_eq!(game.dungeon.depth, 1);
+        assert_eq!(game.deepest_depth(), 2);
+        assert_eq!(game.score(), base + 100);
+    }
+
+    #[test]
+    fn test_morgue_contents() {
+        let mut game = fixed_game();
+        game.message(MessageKind::Bad, "The Kappa hits you for 3.");
+        game.end_run(RunEnd::Killed {
+            by: "Kappa".to_string(),
+        });
+
+        let morgue = game.morgue();
+        assert!(morgue.contains("Killed by a Kappa."));
+        assert!(morgue.contains("Final depth: 1"));
+        assert!(morgue.contains("The Kappa hits you for 3."));
+        assert!(morgue.contains("(empty)"));
+        // The room around the player is drawn with tile characters
+        assert!(morgue.lines().any(|line| line.contains("....@....")));
+    }
+}
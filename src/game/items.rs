@@ -0,0 +1,361 @@
+use crate::{
+    entity::status::StatusKind,
+    game::{
+        Game,
+        config::{HEALING_POTION_AMOUNT, HEALING_POTION_REGEN_TURNS, OFUDA_DAMAGE, OFUDA_RADIUS},
+        state::GameState,
+    },
+    item::{Item, ItemCategory, ItemKind},
+    message::MessageKind,
+};
+use rand::Rng;
+
+impl Game {
+    pub fn open_inventory(&mut self) {
+        self.inventory_cursor = 0;
+        self.state = GameState::ShowInventory;
+    }
+
+    pub fn close_inventory(&mut self) {
+        self.state = GameState::Playing;
+    }
+
+    /// Moves the inventory selection by `delta`, clamped to the item list
+    pub fn move_inventory_cursor(&mut self, delta: i32) {
+        let last = self.inventory().len().saturating_sub(1);
+        self.inventory_cursor = self
+            .inventory_cursor
+            .saturating_add_signed(delta as isize)
+            .min(last);
+    }
+
+    /// Picks up the topmost item under the player
+    ///
+    /// Returns `true` if the action used up the player's turn.
+    pub fn try_pick_up(&mut self) -> bool {
+        let Some(entity) = self.entities.item_at(self.player_pos()) else {
+            self.message(MessageKind::Warning, "There is nothing here to pick up.");
+            return false;
+        };
+
+        let item = *self
+            .entities
+            .item
+            .get(entity)
+            .expect("Items on the floor are items");
+        if self.inventory_mut().add(item).is_err() {
+            self.message(MessageKind::Warning, "Your pack is full.");
+            return false;
+        }
+        self.entities.despawn(entity);
+        self.message(
+            MessageKind::Info,
+            format!("You pick up the {}.", item.name()),
+        );
+        true
+    }
+
+    /// Drops the inventory item at `index` onto the player's tile
+    ///
+    /// Returns `true` if the action used up the player's turn.
+    pub fn drop_item(&mut self, index: usize) -> bool {
+        let Some(item) = self.inventory_mut().remove(index) else {
+            return false;
+        };
+
+        let pos = self.player_pos();
+        Item::spawn(&mut self.entities, item, pos);
+        self.message(MessageKind::Info, format!("You drop the {}.", item.name()));
+        self.clamp_inventory_cursor();
+        true
+    }
+
+    /// Uses (drinks, reads, equips...) the inventory item at `index`
+    ///
+    /// Returns `true` if the action used up the player's turn.
+    pub fn use_item(&mut self, index: usize) -> bool {
+        let mut rng = rand::thread_rng();
+        self.use_item_with_rng(index, &mut rng)
+    }
+
+    /// Uses an item with a provided RNG (for testing)
+    pub fn use_item_with_rng<R: Rng>(&mut self, index: usize, rng: &mut R) -> bool {
+        let Some(item) = self.inventory_mut().remove(index) else {
+            return false;
+        };
+
+        match item.kind {
+            ItemKind::HealingPotion => {
+                let healed = self.player_health_mut().heal(HEALING_POTION_AMOUNT);
+                self.player_mut()
+                    .status
+                    .add(StatusKind::Regenerating, HEALING_POTION_REGEN_TURNS);
+                let text = format!("You drink the {} and recover {} HP.", item.name(), healed);
+                self.message(MessageKind::Good, text);
+            }
+            ItemKind::ScrollOfMapping => {
+                self.dungeon.reveal_map();
+                self.message(MessageKind::Info, "The layout of this floor is revealed.");
+            }
+            ItemKind::ScrollOfTeleport => {
+                self.message(MessageKind::Info, "The scroll whisks you away!");
+                self.teleport_player(rng);
+            }
+            ItemKind::Ofuda => {
+                self.message(MessageKind::Info, "The ofuda bursts into sacred flame!");
+                self.burn_ofuda();
+            }
+            ItemKind::HyakkiEmaki => {
+                // Not used up: it goes back into the same slot and takes no turn
+                self.inventory_mut().items.insert(index, item);
+                self.message(
+                    MessageKind::Info,
+                    "The painted yokai seem to watch you. Carry the emaki to the surface.",
+                );
+                return false;
+            }
+            ItemKind::Key => {
+                self.inventory_mut().items.insert(index, item);
+                self.message(
+                    MessageKind::Info,
+                    "Walk into a locked door to open it with the key.",
+                );
+                return false;
+            }
+            ItemKind::Tanto | ItemKind::Katana | ItemKind::Haori | ItemKind::Yoroi => {
+                let verb = match item.category() {
+                    ItemCategory::Weapon => "wield",
+                    _ => "put on",
+                };
+                self.message(
+                    MessageKind::Info,
+                    format!("You {} the {}.", verb, item.name()),
+                );
+                let (player, stats) = self.player_and_stats_mut();
+                if let Some(previous) = player.equip(item, stats) {
+                    // The slot freed by removing `item` guarantees room for this
+                    self.inventory_mut()
+                        .add(previous)
+                        .expect("Inventory has room for the unequipped item");
+                }
+            }
+        }
+
+        self.clamp_inventory_cursor();
+        true
+    }
+
+    /// Moves the player to a random free tile on the current floor
+    pub(crate) fn teleport_player<R: Rng>(&mut self, rng: &mut R) {
+        if let Some(pos) = self.random_open_position(rng) {
+            self.set_player_pos(pos);
+            self.update_fov();
+        }
+    }
+
+    /// Damages every visible monster within `OFUDA_RADIUS` of the player
+    fn burn_ofuda(&mut self) {
+        let origin = self.player_pos();
+        for monster in self.entities.monsters() {
+            let Some(&pos) = self.entities.position.get(monster) else {
+                continue;
+            };
+            if origin.distance_squared(pos) <= OFUDA_RADIUS * OFUDA_RADIUS
+                && self.dungeon.is_visible(pos)
+            {
+                self.damage_monster(monster, OFUDA_DAMAGE);
+            }
+        }
+    }
+
+    fn clamp_inventory_cursor(&mut self) {
+        self.move_inventory_cursor(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entity::{
+            monster::{Monster, MonsterKind},
+            position::Position,
+        },
+        game::{Game, config::HEALING_POTION_AMOUNT, state::GameState, tests::fixed_game},
+        item::{Item, ItemKind},
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn place_item(game: &mut Game, kind: ItemKind) {
+        let pos = game.player_pos();
+        Item::spawn(&mut game.entities, Item::new(kind), pos);
+    }
+
+    /// Kind of the topmost item lying at `pos`
+    fn item_kind_at(game: &Game, pos: Position) -> Option<ItemKind> {
+        let entity = game.entities.item_at(pos)?;
+        game.entities.item.get(entity).map(|item| item.kind)
+    }
+
+    #[test]
+    fn test_pick_up_moves_item_to_inventory() {
+        let mut game = fixed_game();
+        place_item(&mut game, ItemKind::HealingPotion);
+
+        assert!(game.try_pick_up());
+        assert!(game.entities.item_at(game.player_pos()).is_none());
+        assert_eq!(game.inventory().len(), 1);
+    }
+
+    #[test]
+    fn test_auto_pickup_when_enabled() {
+        for auto_pickup in [false, true] {
+            let mut game = fixed_game();
+            game.options.auto_pickup = auto_pickup;
+            let pos = game.player_pos().translate(1, 0);
+            Item::spawn(&mut game.entities, Item::new(ItemKind::Katana), pos);
+
+            assert!(game.try_move_player(1, 0));
+            assert_eq!(game.inventory().len(), usize::from(auto_pickup));
+        }
+    }
+
+    #[test]
+    fn test_pick_up_nothing_does_not_use_turn() {
+        let mut game = fixed_game();
+        assert!(!game.try_pick_up());
+    }
+
+    #[test]
+    fn test_pick_up_with_full_inventory_leaves_item() {
+        let mut game = fixed_game();
+        while !game.inventory().is_full() {
+            game.inventory_mut()
+                .add(Item::new(ItemKind::Tanto))
+                .unwrap();
+        }
+        place_item(&mut game, ItemKind::Katana);
+
+        assert!(!game.try_pick_up());
+        assert_eq!(
+            item_kind_at(&game, game.player_pos()),
+            Some(ItemKind::Katana)
+        );
+    }
+
+    #[test]
+    fn test_dropped_item_stays_on_floor() {
+        let mut game = fixed_game();
+        game.inventory_mut()
+            .add(Item::new(ItemKind::Ofuda))
+            .unwrap();
+
+        assert!(game.drop_item(0));
+        assert!(game.inventory().is_empty());
+        assert_eq!(
+            item_kind_at(&game, game.player_pos()),
+            Some(ItemKind::Ofuda)
+        );
+    }
+
+    #[test]
+    fn test_healing_potion_heals_and_is_consumed() {
+        let mut game = fixed_game();
+        game.player_health_mut().hp = 1;
+        game.inventory_mut()
+            .add(Item::new(ItemKind::HealingPotion))
+            .unwrap();
+
+        assert!(game.use_item(0));
+        assert_eq!(game.player_health().hp, 1 + HEALING_POTION_AMOUNT);
+        assert!(game.inventory().is_empty());
+    }
+
+    #[test]
+    fn test_scroll_of_mapping_reveals_map() {
+        let mut game = fixed_game();
+        game.inventory_mut()
+            .add(Item::new(ItemKind::ScrollOfMapping))
+            .unwrap();
+
+        game.use_item(0);
+        assert!(game.dungeon.is_explored(Position { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn test_scroll_of_teleport_moves_to_open_tile() {
+        let mut game = fixed_game();
+        game.inventory_mut()
+            .add(Item::new(ItemKind::ScrollOfTeleport))
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+
+        game.use_item_with_rng(0, &mut rng);
+        assert!(game.dungeon.is_walkable(game.player_pos()));
+    }
+
+    #[test]
+    fn test_equipping_swaps_into_inventory() {
+        let mut game = fixed_game();
+        let (player, stats) = game.player_and_stats_mut();
+        player.equip(Item::new(ItemKind::Tanto), stats);
+        game.inventory_mut()
+            .add(Item::new(ItemKind::Katana))
+            .unwrap();
+
+        game.use_item(0);
+        assert_eq!(game.player().weapon, Some(Item::new(ItemKind::Katana)));
+        assert_eq!(game.inventory().get(0), Some(&Item::new(ItemKind::Tanto)));
+    }
+
+    #[test]
+    fn test_ofuda_damages_nearby_monsters() {
+        let mut game = fixed_game();
+        let near = game.player_pos().translate(2, 0);
+        let far = Position { x: 36, y: 21 };
+        let near_oni = Monster::spawn(&mut game.entities, MonsterKind::Oni, near);
+        let far_oni = Monster::spawn(&mut game.entities, MonsterKind::Oni, far);
+        game.inventory_mut()
+            .add(Item::new(ItemKind::Ofuda))
+            .unwrap();
+
+        game.use_item(0);
+        let near_health = game.entities.health.get(near_oni).unwrap();
+        let far_health = game.entities.health.get(far_oni).unwrap();
+        assert!(near_health.hp < near_health.max_hp);
+        assert_eq!(far_health.hp, far_health.max_hp);
+    }
+
+    #[test]
+    fn test_ofuda_kills_weak_monsters() {
+        let mut game = fixed_game();
+        let pos = game.player_pos().translate(1, 0);
+        Monster::spawn(&mut game.entities, MonsterKind::Kappa, pos);
+        game.inventory_mut()
+            .add(Item::new(ItemKind::Ofuda))
+            .unwrap();
+
+        game.use_item(0);
+        assert!(game.entities.monsters().is_empty());
+    }
+
+    #[test]
+    fn test_inventory_cursor_is_clamped() {
+        let mut game = fixed_game();
+        for _ in 0..3 {
+            game.inventory_mut()
+                .add(Item::new(ItemKind::Tanto))
+                .unwrap();
+        }
+        game.open_inventory();
+        assert_eq!(game.state, GameState::ShowInventory);
+
+        game.move_inventory_cursor(10);
+        assert_eq!(game.inventory_cursor, 2);
+        game.move_inventory_cursor(-10);
+        assert_eq!(game.inventory_cursor, 0);
+
+        game.inventory_cursor = 2;
+        game.drop_item(2);
+        assert_eq!(game.inventory_cursor, 1);
+    }
+}
@@ -0,0 +1,410 @@
+use crate::{
+    entity::{position::Position, status::StatusKind},
+    game::{
+        Game,
+        config::{BLAST_RADIUS, HASTE_SPELL_TURNS, HEAL_SPELL_AMOUNT},
+        state::GameState,
+        turn::ActionKind,
+    },
+    message::MessageKind,
+    spell::{Spell, SpellTarget, Targeting},
+    world::line::line,
+};
+
+impl Game {
+    pub fn open_spell_menu(&mut self) {
+        self.spell_cursor = 0;
+        self.state = GameState::SelectSpell;
+    }
+
+    pub fn close_spell_menu(&mut self) {
+        self.state = GameState::Playing;
+    }
+
+    /// Moves the spell menu selection by `delta`, clamped to the known spells
+    pub fn move_spell_cursor(&mut self, delta: i32) {
+        let last = self.player().spellbook.spells.len().saturating_sub(1);
+        self.spell_cursor = self
+            .spell_cursor
+            .saturating_add_signed(delta as isize)
+            .min(last);
+    }
+
+    /// Chooses the spell at `index` in the spell book
+    ///
+    /// Spells that affect the caster are cast immediately; aimed spells switch
+    /// to `GameState::Targeting`. Returns `true` if the player's turn was used.
+    pub fn select_spell(&mut self, index: usize) -> bool {
+        let Some(&spell) = self.player().spellbook.spells.get(index) else {
+            return false;
+        };
+        if !self.player().spellbook.can_cast(spell) {
+            let text = format!("You don't have enough mana to cast {}.", spell.name());
+            self.message(MessageKind::Warning, text);
+            return false;
+        }
+
+        match spell.target() {
+            SpellTarget::Caster => {
+                self.player_mut().spellbook.spend(spell);
+                self.player_mut().energy.spend(ActionKind::Cast.cost());
+                self.cast_on_self(spell);
+                self.state = GameState::Playing;
+                true
+            }
+            SpellTarget::Tile { range } => {
+                let cursor = self
+                    .nearest_visible_monster(range)
+                    .unwrap_or(self.player_pos());
+                self.targeting = Some(Targeting { spell, cursor });
+                self.state = GameState::Targeting;
+                false
+            }
+        }
+    }
+
+    /// Moves the targeting cursor, keeping it on the map and within the spell's range
+    pub fn move_target_cursor(&mut self, dx: i32, dy: i32) {
+        let Some(targeting) = self.targeting else {
+            return;
+        };
+        let SpellTarget::Tile { range } = targeting.spell.target() else {
+            return;
+        };
+
+        let next = targeting.cursor.translate(dx, dy);
+        if self.dungeon.get_tile(next).is_some()
+            && self.player_pos().distance_squared(next) <= range * range
+        {
+            self.targeting = Some(Targeting {
+                cursor: next,
+                ..targeting
+            });
+        }
+    }
+
+    pub fn cancel_targeting(&mut self) {
+        self.targeting = None;
+        self.state = GameState::Playing;
+    }
+
+    /// Casts the spell being aimed at the cursor position
+    ///
+    /// Returns `true` if the spell was cast (and the player's turn used).
+    pub fn cast_at_target(&mut self) -> bool {
+        let Some(Targeting { spell, cursor }) = self.targeting else {
+            return false;
+        };
+        if !self.is_valid_target(spell, cursor) {
+            self.message(MessageKind::Warning, "You can't aim there.");
+            return false;
+        }
+        if !self.player_mut().spellbook.spend(spell) {
+            return false;
+        }
+        self.player_mut().energy.spend(ActionKind::Cast.cost());
+
+        match spell {
+            Spell::Bolt | Spell::Blast => {
+                self.message(MessageKind::Info, format!("You cast {}.", spell.name()));
+                for pos in self.spell_area(spell, cursor) {
+                    if let Some(monster) = self.entities.monster_at(pos) {
+                        self.damage_monster(monster, spell.damage());
+                    }
+                }
+            }
+            Spell::Blink => {
+                self.message(MessageKind::Info, "You vanish and reappear nearby.");
+                self.set_player_pos(cursor);
+                self.update_fov();
+            }
+            Spell::Heal | Spell::Reveal | Spell::Haste => self.cast_on_self(spell),
+        }
+
+        self.targeting = None;
+        self.state = GameState::Playing;
+        true
+    }
+
+    /// Whether the aimed `spell` can be cast at `target`
+    ///
+    /// Targets must be in view, so walls always block aimed spells.
+    pub fn is_valid_target(&self, spell: Spell, target: Position) -> bool {
+        if target == self.player_pos() || !self.dungeon.is_visible(target) {
+            return false;
+        }
+        match spell {
+            Spell::Blink => !self.is_blocked(target),
+            _ => true,
+        }
+    }
+
+    /// Tiles affected by `spell` when aimed at `target`, for casting and for previews
+    ///
+    /// ```text
+    /// Bolt: stops at the first monster or wall     Blast: radius around target,
+    ///   @ * * * k . . x                              blocked by walls
+    ///                                                     * * *
+    ///                                                   * * x * #
+    ///                                                     * * * # .
+    /// ```
+    pub fn spell_area(&self, spell: Spell, target: Position) -> Vec<Position> {
+        match spell {
+            Spell::Bolt => {
+                let mut path = Vec::new();
+                for pos in line(self.player_pos(), target) {
+                    path.push(pos);
+                    let blocked = self.dungeon.get_tile(pos).is_none_or(|t| t.blocks_sight());
+                    if blocked || self.entities.monster_at(pos).is_some() {
+                        break;
+                    }
+                }
+                path
+            }
+            Spell::Blast => {
+                let r = BLAST_RADIUS;
+                (-r..=r)
+                    .flat_map(|dy| (-r..=r).map(move |dx| target.translate(dx, dy)))
+                    .filter(|&pos| target.distance_squared(pos) <= r * r)
+                    .filter(|&pos| self.dungeon.is_walkable(pos))
+                    .filter(|&pos| self.dungeon.has_line_of_sight(target, pos))
+                    .collect()
+            }
+            Spell::Blink => vec![target],
+            Spell::Heal | Spell::Reveal | Spell::Haste => vec![self.player_pos()],
+        }
+    }
+
+    fn cast_on_self(&mut self, spell: Spell) {
+        match spell {
+            Spell::Heal => {
+                let healed = self.player_health_mut().heal(HEAL_SPELL_AMOUNT);
+                let text = format!("You cast {} and recover {} HP.", spell.name(), healed);
+                self.message(MessageKind::Good, text);
+            }
+            Spell::Reveal => {
+                self.dungeon.reveal_map();
+                let text = format!("You cast {}. The floor is laid bare.", spell.name());
+                self.message(MessageKind::Info, text);
+            }
+            Spell::Haste => {
+                self.player_mut()
+                    .status
+                    .add(StatusKind::Hasted, HASTE_SPELL_TURNS);
+                let text = format!("You cast {} and the world slows around you.", spell.name());
+                self.message(MessageKind::Good, text);
+            }
+            Spell::Bolt | Spell::Blast | Spell::Blink => {}
+        }
+    }
+
+    /// Position of the closest visible monster within `range`, used as the initial cursor
+    fn nearest_visible_monster(&self, range: i32) -> Option<Position> {
+        let origin = self.player_pos();
+        self.entities
+            .monsters()
+            .into_iter()
+            .filter_map(|monster| self.entities.position.get(monster).copied())
+            .filter(|&pos| self.dungeon.is_visible(pos))
+            .filter(|&pos| origin.distance_squared(pos) <= range * range)
+            .min_by_key(|&pos| origin.distance_squared(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ecs::Health,
+        entity::monster::{Monster, MonsterKind},
+        game::tests::fixed_game,
+        world::tile::TileType,
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn spell_index(game: &Game, spell: Spell) -> usize {
+        game.player()
+            .spellbook
+            .spells
+            .iter()
+            .position(|&s| s == spell)
+            .unwrap()
+    }
+
+    fn add_monster(game: &mut Game, kind: MonsterKind, dx: i32, dy: i32) -> Position {
+        let pos = game.player_pos().translate(dx, dy);
+        Monster::spawn(&mut game.entities, kind, pos);
+        pos
+    }
+
+    fn health_at(game: &Game, pos: Position) -> Health {
+        let monster = game.entities.monster_at(pos).unwrap();
+        *game.entities.health.get(monster).unwrap()
+    }
+
+    #[test]
+    fn test_heal_is_cast_immediately() {
+        let mut game = fixed_game();
+        game.player_health_mut().hp = 1;
+        game.open_spell_menu();
+
+        assert!(game.select_spell(spell_index(&game, Spell::Heal)));
+        assert_eq!(game.player_health().hp, 1 + HEAL_SPELL_AMOUNT);
+        assert_eq!(game.state, GameState::Playing);
+        assert_eq!(
+            game.player().spellbook.mana,
+            game.player().spellbook.max_mana - Spell::Heal.mana_cost()
+        );
+    }
+
+    #[test]
+    fn test_haste_doubles_actions_per_turn() {
+        let mut game = fixed_game();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(game.select_spell(spell_index(&game, Spell::Haste)));
+        game.end_turn_with_rng(&mut rng);
+        assert!(game.player().status.has(StatusKind::Hasted));
+        let turn = game.turn;
+
+        for _ in 0..4 {
+            game.end_turn_with_rng(&mut rng);
+        }
+        assert_eq!(game.turn, turn + 2);
+    }
+
+    #[test]
+    fn test_not_enough_mana() {
+        let mut game = fixed_game();
+        game.player_mut().spellbook.mana = 0;
+        game.open_spell_menu();
+
+        assert!(!game.select_spell(spell_index(&game, Spell::Reveal)));
+        assert_eq!(game.state, GameState::SelectSpell);
+    }
+
+    #[test]
+    fn test_aimed_spell_enters_targeting_on_nearest_monster() {
+        let mut game = fixed_game();
+        add_monster(&mut game, MonsterKind::Oni, 4, 0);
+        let near = add_monster(&mut game, MonsterKind::Oni, 0, 2);
+
+        assert!(!game.select_spell(spell_index(&game, Spell::Bolt)));
+        assert_eq!(game.state, GameState::Targeting);
+        assert_eq!(game.targeting.map(|t| t.cursor), Some(near));
+    }
+
+    #[test]
+    fn test_target_cursor_stays_in_range() {
+        let mut game = fixed_game();
+        game.select_spell(spell_index(&game, Spell::Blink));
+        for _ in 0..20 {
+            game.move_target_cursor(1, 0);
+        }
+        let SpellTarget::Tile { range } = Spell::Blink.target() else {
+            unreachable!()
+        };
+        let cursor = game.targeting.unwrap().cursor;
+        assert_eq!(cursor, game.player_pos().translate(range, 0));
+    }
+
+    #[test]
+    fn test_bolt_hits_first_monster_only() {
+        let mut game = fixed_game();
+        let first = add_monster(&mut game, MonsterKind::Oni, 2, 0);
+        let second = add_monster(&mut game, MonsterKind::Oni, 3, 0);
+
+        game.select_spell(spell_index(&game, Spell::Bolt));
+        game.targeting = Some(Targeting {
+            spell: Spell::Bolt,
+            cursor: second,
+        });
+        assert!(game.cast_at_target());
+
+        let first_health = health_at(&game, first);
+        let second_health = health_at(&game, second);
+        assert_eq!(first_health.hp, first_health.max_hp - Spell::Bolt.damage());
+        assert_eq!(second_health.hp, second_health.max_hp);
+    }
+
+    #[test]
+    fn test_bolt_stops_at_walls() {
+        let mut game = fixed_game();
+        let wall = game.player_pos().translate(2, 0);
+        game.dungeon.tiles[wall.y as usize][wall.x as usize] = TileType::Wall;
+        let target = game.player_pos().translate(4, 0);
+
+        let path = game.spell_area(Spell::Bolt, target);
+        assert_eq!(path.last(), Some(&wall));
+    }
+
+    #[test]
+    fn test_blast_kills_through_damage_pipeline() {
+        let mut game = fixed_game();
+        let target = add_monster(&mut game, MonsterKind::Kappa, 3, 0);
+        let kappa = game.entities.monster_at(target).unwrap();
+        game.entities.health.get_mut(kappa).unwrap().hp = 1;
+
+        game.select_spell(spell_index(&game, Spell::Blast));
+        game.targeting = Some(Targeting {
+            spell: Spell::Blast,
+            cursor: target,
+        });
+        assert!(game.cast_at_target());
+        assert!(game.entities.monsters().is_empty());
+    }
+
+    #[test]
+    fn test_blast_does_not_pass_walls() {
+        let mut game = fixed_game();
+        let center = game.player_pos().translate(-3, 0);
+        // Wall between the blast center and a floor tile two steps to its right
+        let wall = center.translate(1, 0);
+        game.dungeon.tiles[wall.y as usize][wall.x as usize] = TileType::Wall;
+
+        let area = game.spell_area(Spell::Blast, center);
+        assert!(area.contains(&center.translate(-1, 0)));
+        assert!(!area.contains(&wall));
+        assert!(!area.contains(&center.translate(2, 0)));
+    }
+
+    #[test]
+    fn test_blink_moves_player() {
+        let mut game = fixed_game();
+        let target = game.player_pos().translate(-3, 1);
+        game.select_spell(spell_index(&game, Spell::Blink));
+        game.targeting = Some(Targeting {
+            spell: Spell::Blink,
+            cursor: target,
+        });
+
+        assert!(game.cast_at_target());
+        assert_eq!(game.player_pos(), target);
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_blink_onto_wall_is_rejected() {
+        let mut game = fixed_game();
+        let wall = Position { x: 40, y: 19 };
+        assert_eq!(game.dungeon.get_tile(wall), Some(TileType::Wall));
+        game.select_spell(spell_index(&game, Spell::Blink));
+        game.targeting = Some(Targeting {
+            spell: Spell::Blink,
+            cursor: wall,
+        });
+
+        let mana = game.player().spellbook.mana;
+        assert!(!game.cast_at_target());
+        assert_eq!(game.player().spellbook.mana, mana);
+    }
+
+    #[test]
+    fn test_cancel_targeting() {
+        let mut game = fixed_game();
+        game.select_spell(spell_index(&game, Spell::Blink));
+        game.cancel_targeting();
+        assert_eq!(game.state, GameState::Playing);
+        assert!(game.targeting.is_none());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    game::{Game, state::GameState},
+    message::MessageKind,
+};
+
+impl Game {
+    /// Adds a message to the log, stamped with the current turn
+    pub fn message(&mut self, kind: MessageKind, text: impl Into<String>) {
+        self.messages.push(self.turn, kind, text);
+    }
+
+    pub fn open_message_history(&mut self) {
+        self.message_scroll = 0;
+        self.state = GameState::MessageHistory;
+    }
+
+    pub fn close_message_history(&mut self) {
+        self.state = GameState::Playing;
+    }
+
+    /// Scrolls the history by `delta` lines; negative values go back to older messages
+    ///
+    /// `message_scroll` counts how many messages the view is above the newest one.
+    pub fn scroll_message_history(&mut self, delta: i32) {
+        let oldest = self.messages.len().saturating_sub(1);
+        self.message_scroll = self
+            .message_scroll
+            .saturating_add_signed(-delta as isize)
+            .min(oldest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_is_stamped_with_turn() {
+        let mut game = Game::new();
+        game.turn = 7;
+        game.message(MessageKind::Warning, "Careful");
+
+        let last = game.messages.last().unwrap();
+        assert_eq!(last.turn, 7);
+        assert_eq!(last.kind, MessageKind::Warning);
+        assert_eq!(last.text, "Careful");
+    }
+
+    #[test]
+    fn test_history_scroll_is_clamped() {
+        let mut game = Game::new();
+        for i in 0..5 {
+            game.message(MessageKind::Info, format!("message {}", i));
+        }
+        game.open_message_history();
+        assert_eq!(game.state, GameState::MessageHistory);
+
+        game.scroll_message_history(1);
+        assert_eq!(game.message_scroll, 0);
+        game.scroll_message_history(-100);
+        assert_eq!(game.message_scroll, game.messages.len() - 1);
+
+        game.close_message_history();
+        assert_eq!(game.state, GameState::Playing);
+    }
+}
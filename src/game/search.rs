@@ -0,0 +1,149 @@
+use crate::{
+    entity::position::Position,
+    game::{
+        Game,
+        config::{PERCEPTION_CHANCE, SEARCH_CHANCE},
+    },
+    message::MessageKind,
+    world::tile::TileType,
+};
+use rand::Rng;
+
+impl Game {
+    /// Searches the tiles around the player for hidden traps and secret doors
+    ///
+    /// Always uses up the player's turn, found or not.
+    pub fn search(&mut self) -> bool {
+        let mut rng = rand::thread_rng();
+        self.search_with_rng(&mut rng)
+    }
+
+    /// Searches using a provided RNG (for testing)
+    pub fn search_with_rng<R: Rng>(&mut self, rng: &mut R) -> bool {
+        if self.reveal_hidden_nearby(SEARCH_CHANCE, rng) == 0 {
+            self.message(MessageKind::Info, "You search around but find nothing.");
+        }
+        true
+    }
+
+    /// Passive perception: gives the player a small chance to notice each
+    /// hidden thing next to them as they walk by
+    pub(crate) fn notice_hidden<R: Rng>(&mut self, rng: &mut R) {
+        self.reveal_hidden_nearby(PERCEPTION_CHANCE, rng);
+    }
+
+    /// Reveals each hidden trap and secret door on or next to the player's
+    /// tile with probability `chance`, returning how many were found
+    ///
+    /// A secret door that is found turns into an ordinary closed door.
+    fn reveal_hidden_nearby<R: Rng>(&mut self, chance: f64, rng: &mut R) -> usize {
+        let origin = self.player_pos();
+        let mut found = Vec::new();
+        for trap in &mut self.dungeon.traps {
+            if trap.hidden && is_near(origin, trap.position) && rng.gen_bool(chance) {
+                trap.hidden = false;
+                found.push(trap.kind.name());
+            }
+        }
+        for (dx, dy) in NEIGHBOURS {
+            let pos = origin.translate(dx, dy);
+            if self.dungeon.get_tile(pos) == Some(TileType::DoorSecret) && rng.gen_bool(chance) {
+                self.set_tile(pos, TileType::DoorClosed);
+                found.push("secret door");
+            }
+        }
+
+        for name in &found {
+            self.message(MessageKind::Good, format!("You find a {}!", name));
+        }
+        found.len()
+    }
+}
+
+/// The eight tiles around a position
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Whether `b` is `a` or one of its eight neighbours
+fn is_near(a: Position, b: Position) -> bool {
+    (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entity::position::Position,
+        game::tests::fixed_game,
+        world::{
+            tile::TileType,
+            trap::{Trap, TrapKind},
+        },
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_search_finds_adjacent_traps() {
+        let mut game = fixed_game();
+        let near = game.player_pos().translate(1, 1);
+        let far = game.player_pos().translate(3, 0);
+        game.dungeon.traps.push(Trap::new(TrapKind::SpikePit, near));
+        game.dungeon.traps.push(Trap::new(TrapKind::Alarm, far));
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..20 {
+            assert!(game.search_with_rng(&mut rng));
+        }
+        assert!(game.dungeon.known_trap_at(near).is_some());
+        assert!(game.dungeon.known_trap_at(far).is_none());
+    }
+
+    #[test]
+    fn test_search_finds_secret_doors() {
+        let mut game = fixed_game();
+        let door = game.player_pos().translate(0, -1);
+        let far = Position { x: 5, y: 5 };
+        game.set_tile(door, TileType::DoorSecret);
+        game.set_tile(far, TileType::DoorSecret);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..20 {
+            game.search_with_rng(&mut rng);
+        }
+        assert_eq!(game.dungeon.get_tile(door), Some(TileType::DoorClosed));
+        assert_eq!(game.dungeon.get_tile(far), Some(TileType::DoorSecret));
+    }
+
+    #[test]
+    fn test_failed_search_is_reported() {
+        let mut game = fixed_game();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(game.search_with_rng(&mut rng));
+        assert!(
+            game.messages
+                .iter()
+                .any(|m| m.text.contains("find nothing"))
+        );
+    }
+
+    #[test]
+    fn test_bumping_secret_door_reveals_nothing() {
+        let mut game = fixed_game();
+        let door = game.player_pos().translate(1, 0);
+        game.set_tile(door, TileType::DoorSecret);
+        let start = game.player_pos();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(!game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.player_pos(), start);
+        assert_eq!(game.dungeon.get_tile(door), Some(TileType::DoorSecret));
+    }
+}
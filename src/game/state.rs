@@ -1,9 +1,17 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum GameState {
     MainMenu,
     #[default]
     Playing,
     ShowInventory,
     SelectSpell,
+    /// Aiming a spell with the targeting cursor
+    Targeting,
+    /// Reading the full message log
+    MessageHistory,
+    /// Choosing which of several adjacent doors to close
+    CloseDoor,
     GameOver,
 }
@@ -0,0 +1,203 @@
+use crate::{
+    entity::{position::Position, status::StatusKind},
+    game::{
+        Game,
+        config::{BURN_DAMAGE, BURN_TURNS, DROWN_DAMAGE, FALL_DAMAGE, LAVA_DAMAGE, SWIM_TURNS},
+    },
+    message::MessageKind,
+    world::tile::TileType,
+};
+use rand::Rng;
+
+impl Game {
+    /// Applies what happens as the player steps from `from` onto their
+    /// current tile
+    ///
+    /// Water puts out flames, deep water starts the player swimming, lava
+    /// sets them alight and a chasm drops them onto the floor below.
+    pub(crate) fn enter_terrain<R: Rng>(&mut self, from: Position, rng: &mut R) {
+        let tile = self.dungeon.get_tile(self.player_pos());
+        if tile != Some(TileType::DeepWater) {
+            self.player_mut().status.remove(StatusKind::Swimming);
+        }
+
+        match tile {
+            Some(TileType::ShallowWater) => self.douse(),
+            Some(TileType::DeepWater) => {
+                self.douse();
+                if self.dungeon.get_tile(from) != Some(TileType::DeepWater) {
+                    self.player_mut()
+                        .status
+                        .add(StatusKind::Swimming, SWIM_TURNS);
+                    self.message(
+                        MessageKind::Warning,
+                        "You plunge into deep water and start to swim.",
+                    );
+                }
+            }
+            Some(TileType::Lava) => {
+                self.message(MessageKind::Bad, "You step into the lava!");
+                self.player_mut()
+                    .status
+                    .add(StatusKind::Burning, BURN_TURNS);
+            }
+            Some(TileType::Chasm) => {
+                self.message(MessageKind::Bad, "You fall into the chasm!");
+                self.fall_to_next_depth("Fell into a chasm", rng);
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies one turn spent on the player's current tile: lava burns, and
+    /// deep water drowns a swimmer who is out of breath
+    pub(crate) fn apply_terrain_effects(&mut self) {
+        match self.dungeon.get_tile(self.player_pos()) {
+            Some(TileType::Lava) => {
+                self.message(MessageKind::Bad, "The lava sears you!");
+                self.player_mut()
+                    .status
+                    .add(StatusKind::Burning, BURN_TURNS);
+                self.hurt_player(LAVA_DAMAGE, "Burned to death in lava");
+            }
+            Some(TileType::DeepWater) if !self.player().status.has(StatusKind::Swimming) => {
+                self.message(MessageKind::Bad, "You are drowning!");
+                self.hurt_player(DROWN_DAMAGE, "Drowned");
+            }
+            _ => {}
+        }
+    }
+
+    /// Deals the damage of one turn on fire
+    pub(crate) fn burn(&mut self) {
+        self.message(MessageKind::Bad, "You burn!");
+        self.hurt_player(BURN_DAMAGE, "Burned to death");
+    }
+
+    /// Puts out the flames if the player is burning
+    fn douse(&mut self) {
+        if self.player_mut().status.remove(StatusKind::Burning) {
+            self.message(MessageKind::Good, "The water puts out the flames.");
+        }
+    }
+
+    /// Drops the player onto a random spot of the floor below
+    ///
+    /// The landing hurts; `cause` is how the run ends if it kills them.
+    pub(crate) fn fall_to_next_depth<R: Rng>(&mut self, cause: &str, rng: &mut R) {
+        self.hurt_player(FALL_DAMAGE, cause);
+        if self.is_game_over() {
+            return;
+        }
+
+        let depth = self.dungeon.depth + 1;
+        self.change_floor(depth);
+        let landing = self
+            .random_open_position(rng)
+            .unwrap_or(self.dungeon.player_start);
+        self.set_player_pos(landing);
+        self.update_fov();
+        self.message(
+            MessageKind::Warning,
+            format!("You land hard on depth {}.", depth),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entity::{position::Position, status::StatusKind},
+        game::{
+            Game,
+            config::{FALL_DAMAGE, PLAYER_MAX_HP},
+            state::GameState,
+            tests::fixed_game,
+        },
+        world::tile::TileType,
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Fixed-room game with a tile of type `tile` just east of the player
+    fn game_beside(tile: TileType) -> (Game, Position) {
+        let mut game = fixed_game();
+        let pos = game.player_pos().translate(1, 0);
+        game.dungeon.tiles[pos.y as usize][pos.x as usize] = tile;
+        game.update_fov();
+        (game, pos)
+    }
+
+    #[test]
+    fn test_wading_takes_an_extra_turn() {
+        let (mut game, water) = game_beside(TileType::ShallowWater);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        game.end_turn_with_rng(&mut rng);
+        assert_eq!(game.player_pos(), water);
+        assert_eq!(game.turn, 2);
+    }
+
+    #[test]
+    fn test_water_puts_out_flames() {
+        let (mut game, _) = game_beside(TileType::ShallowWater);
+        game.player_mut().status.add(StatusKind::Burning, 3);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        assert!(!game.player().status.has(StatusKind::Burning));
+    }
+
+    #[test]
+    fn test_lava_burns() {
+        let (mut game, _) = game_beside(TileType::Lava);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        assert!(game.player().status.has(StatusKind::Burning));
+        game.end_turn();
+        assert!(game.player_health().hp < PLAYER_MAX_HP);
+    }
+
+    #[test]
+    fn test_swimmer_drowns_once_out_of_breath() {
+        let (mut game, water) = game_beside(TileType::DeepWater);
+        game.player_health_mut().hp = 1;
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        assert!(game.player().status.has(StatusKind::Swimming));
+        for _ in 0..20 {
+            if game.is_game_over() {
+                break;
+            }
+            game.end_turn();
+        }
+
+        assert_eq!(game.player_pos(), water);
+        assert_eq!(game.state, GameState::GameOver);
+        assert_eq!(game.end_cause(), "Drowned");
+    }
+
+    #[test]
+    fn test_leaving_deep_water_stops_swimming() {
+        let (mut game, _) = game_beside(TileType::DeepWater);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        game.try_move_player_with_rng(-1, 0, &mut rng);
+        assert!(!game.player().status.has(StatusKind::Swimming));
+    }
+
+    #[test]
+    fn test_chasm_drops_player_to_next_depth() {
+        let (mut game, _) = game_beside(TileType::Chasm);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.dungeon.depth, 2);
+        assert!(game.dungeon.is_walkable(game.player_pos()));
+        assert_eq!(game.player_health().hp, PLAYER_MAX_HP - FALL_DAMAGE);
+        assert!(game.levels.contains_key(&1));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    entity::{monster::AiState, status::StatusKind},
+    game::{
+        Game,
+        config::{DART_DAMAGE, POISON_TURNS, SPIKE_PIT_DAMAGE},
+    },
+    message::MessageKind,
+    world::trap::TrapKind,
+};
+use rand::Rng;
+
+impl Game {
+    /// Sets off the trap under the player, if there is one
+    ///
+    /// Known traps go off just the same; finding a trap only helps the player
+    /// walk around it. Monsters are light-footed enough never to trigger them.
+    pub(crate) fn spring_trap<R: Rng>(&mut self, rng: &mut R) {
+        let Some(index) = self.dungeon.trap_index_at(self.player_pos()) else {
+            return;
+        };
+        let trap = &mut self.dungeon.traps[index];
+        trap.hidden = false;
+        let kind = trap.kind;
+
+        match kind {
+            TrapKind::SpikePit => {
+                self.message(MessageKind::Bad, "You fall into a spike pit!");
+                self.hurt_player(SPIKE_PIT_DAMAGE, "Impaled in a spike pit");
+            }
+            TrapKind::PoisonDart => {
+                self.message(MessageKind::Bad, "A poisoned dart shoots out of the wall!");
+                self.player_mut()
+                    .status
+                    .add(StatusKind::Poisoned, POISON_TURNS);
+                self.hurt_player(DART_DAMAGE, "Shot by a poison dart");
+            }
+            TrapKind::Alarm => {
+                self.message(
+                    MessageKind::Warning,
+                    "A bell clangs! The yokai on this floor stir.",
+                );
+                for monster in self.entities.monsters() {
+                    if let Some(ai) = self.entities.ai.get_mut(monster)
+                        && *ai == AiState::Idle
+                    {
+                        *ai = AiState::Wander;
+                    }
+                }
+            }
+            TrapKind::Teleport => {
+                self.message(
+                    MessageKind::Warning,
+                    "The floor glows and you are whisked away!",
+                );
+                self.teleport_player(rng);
+            }
+            TrapKind::Trapdoor => {
+                self.message(MessageKind::Bad, "A trapdoor opens beneath you!");
+                self.fall_to_next_depth("Fell through a trapdoor", rng);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entity::{
+            monster::{AiState, Monster, MonsterKind},
+            position::Position,
+            status::StatusKind,
+        },
+        game::{
+            Game,
+            config::{PLAYER_MAX_HP, SPIKE_PIT_DAMAGE},
+            tests::fixed_game,
+        },
+        world::trap::{Trap, TrapKind},
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Fixed-room game with a hidden trap of `kind` just east of the player
+    fn game_with_trap(kind: TrapKind) -> (Game, Position) {
+        let mut game = fixed_game();
+        let pos = game.player_pos().translate(1, 0);
+        game.dungeon.traps.push(Trap::new(kind, pos));
+        (game, pos)
+    }
+
+    #[test]
+    fn test_spike_pit_hurts_and_is_revealed() {
+        let (mut game, trap) = game_with_trap(TrapKind::SpikePit);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(game.dungeon.known_trap_at(trap).is_none());
+        assert!(game.try_move_player_with_rng(1, 0, &mut rng));
+        assert_eq!(game.player_health().hp, PLAYER_MAX_HP - SPIKE_PIT_DAMAGE);
+        assert!(game.dungeon.known_trap_at(trap).is_some());
+    }
+
+    #[test]
+    fn test_poison_dart_poisons() {
+        let (mut game, _) = game_with_trap(TrapKind::PoisonDart);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        assert!(game.player().status.has(StatusKind::Poisoned));
+        let hp = game.player_health().hp;
+        game.end_turn();
+        assert!(game.player_health().hp < hp);
+    }
+
+    #[test]
+    fn test_alarm_wakes_monsters() {
+        let (mut game, _) = game_with_trap(TrapKind::Alarm);
+        let oni = Monster::spawn(
+            &mut game.entities,
+            MonsterKind::Oni,
+            Position { x: 5, y: 5 },
+        );
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        assert_eq!(game.entities.ai.get(oni), Some(&AiState::Wander));
+    }
+
+    #[test]
+    fn test_teleport_trap_moves_player() {
+        let (mut game, trap) = game_with_trap(TrapKind::Teleport);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        assert_ne!(game.player_pos(), trap);
+        assert!(game.dungeon.is_walkable(game.player_pos()));
+    }
+
+    #[test]
+    fn test_trapdoor_drops_to_next_depth() {
+        let (mut game, _) = game_with_trap(TrapKind::Trapdoor);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        game.try_move_player_with_rng(1, 0, &mut rng);
+        assert_eq!(game.dungeon.depth, 2);
+        assert!(!game.levels[&1].traps[0].hidden);
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{
+    ecs::Entity,
+    entity::status::{StatusEffects, StatusKind},
+};
+use serde::{Deserialize, Serialize};
+
+/// Energy an actor needs to have built up before it can act
+pub const ENERGY_TO_ACT: i32 = 100;
+
+/// Energy an actor of ordinary speed gains each tick
+pub const NORMAL_SPEED: i32 = 10;
+
+/// Ticks in a game turn: the time an actor of normal speed takes to build up
+/// the energy for one action
+///
+/// Turn-based upkeep (status effects, terrain, mana) runs once per turn, so
+/// a hasted player fits two actions into each turn of poison or burning.
+pub const TICKS_PER_TURN: u32 = (ENERGY_TO_ACT / NORMAL_SPEED) as u32;
+
+/// The kinds of action that cost an actor energy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    /// Stepping onto a neighbouring tile; slow terrain multiplies the cost
+    Move,
+    Attack,
+    Cast,
+    /// Taking the stairs, up or down
+    Descend,
+    /// Anything else: using an item, opening a door, searching, waiting
+    Other,
+}
+
+impl ActionKind {
+    /// Energy the action uses up
+    ///
+    /// Every cost is at least `ENERGY_TO_ACT`, more than any actor gains in a
+    /// tick, so an actor that has acted always has to wait before acting again.
+    pub fn cost(&self) -> i32 {
+        match self {
+            ActionKind::Move | ActionKind::Attack | ActionKind::Descend | ActionKind::Other => {
+                ENERGY_TO_ACT
+            }
+            ActionKind::Cast => ENERGY_TO_ACT * 6 / 5,
+        }
+    }
+}
+
+/// Energy built up towards an actor's next action
+///
+/// Every tick an actor gains energy equal to its speed, and it may act once
+/// it has `ENERGY_TO_ACT`. Acting spends the action's cost, which can leave
+/// the actor in debt for slow actions.
+///
+/// ```text
+///   speed 20 (tengu)   ├──────act──────act──────act──────act──  2 per turn
+///   speed 10 (player)  ├──────────────act──────────────act────  1 per turn
+///   speed  8 (oni)     ├──────────────────act────────────────── 4 every 5 turns
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Energy(i32);
+
+impl Energy {
+    /// Enough energy to act straight away
+    pub fn ready() -> Self {
+        Energy(ENERGY_TO_ACT)
+    }
+
+    pub fn gain(&mut self, speed: i32) {
+        self.0 += speed;
+    }
+
+    pub fn can_act(&self) -> bool {
+        self.0 >= ENERGY_TO_ACT
+    }
+
+    pub fn spend(&mut self, cost: i32) {
+        self.0 -= cost;
+    }
+}
+
+/// Counts ticks and marks where one game turn ends and the next begins
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnClock {
+    /// Ticks into the current turn
+    ticks: u32,
+}
+
+impl TurnClock {
+    /// Advances one tick, returning `true` if that completes a turn
+    pub fn tick(&mut self) -> bool {
+        self.ticks += 1;
+        if self.ticks < TICKS_PER_TURN {
+            return false;
+        }
+        self.ticks = 0;
+        true
+    }
+}
+
+/// Something that takes turns: the player, or a monster on the current floor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Actor {
+    Player,
+    Monster(Entity),
+}
+
+/// What happens next in the game's timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Next {
+    /// `Actor` has the energy to act, and should spend it now
+    Act(Actor),
+    /// A game turn has gone by, and turn-based upkeep is due
+    TurnEnds,
+}
+
+/// The energy and speed of everyone a `Scheduler` hands turns to
+pub trait Actors {
+    /// The monsters taking turns, in the order they get to act on a tie
+    fn monsters(&self) -> Vec<Entity>;
+    fn energy_mut(&mut self, actor: Actor) -> &mut Energy;
+    /// Energy `actor` gains each tick, haste and slow included
+    fn speed(&self, actor: Actor) -> i32;
+}
+
+/// Decides who acts next by running the clock until someone has the energy
+///
+/// Each call hands back one actor ready to act, or ticks the clock: every
+/// actor gains its speed in energy, and the end of every `TICKS_PER_TURN`
+/// ticks is reported as a turn ending. Monsters ready on the same tick as the
+/// player go first, so the player is only asked for input once everything
+/// else has moved. The actor handed back has to spend some energy before the
+/// next call, or it will be handed back again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scheduler {
+    clock: TurnClock,
+}
+
+impl Scheduler {
+    pub fn next(&mut self, actors: &mut impl Actors) -> Next {
+        let monsters = actors.monsters();
+        let everyone = || {
+            monsters
+                .iter()
+                .copied()
+                .map(Actor::Monster)
+                .chain([Actor::Player])
+        };
+        loop {
+            if let Some(actor) = everyone().find(|&actor| actors.energy_mut(actor).can_act()) {
+                return Next::Act(actor);
+            }
+            let turn_ends = self.clock.tick();
+            for actor in everyone() {
+                let speed = actors.speed(actor);
+                actors.energy_mut(actor).gain(speed);
+            }
+            if turn_ends {
+                return Next::TurnEnds;
+            }
+        }
+    }
+}
+
+/// An actor's speed once haste and slow are taken into account: haste
+/// doubles it and slow halves it, so having both cancels out
+pub fn effective_speed(base: i32, status: &StatusEffects) -> i32 {
+    let mut speed = base;
+    if status.has(StatusKind::Hasted) {
+        speed *= 2;
+    }
+    if status.has(StatusKind::Slowed) {
+        speed /= 2;
+    }
+    speed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::Entities;
+
+    /// A player of `speeds[0]` and a monster for each of the other speeds,
+    /// all starting with no energy
+    struct Crowd {
+        monsters: Vec<Entity>,
+        speeds: Vec<i32>,
+        energy: Vec<Energy>,
+    }
+
+    impl Crowd {
+        fn new(speeds: &[i32]) -> Self {
+            let mut entities = Entities::default();
+            Crowd {
+                monsters: speeds[1..].iter().map(|_| entities.spawn()).collect(),
+                speeds: speeds.to_vec(),
+                energy: vec![Energy::default(); speeds.len()],
+            }
+        }
+
+        fn index(&self, actor: Actor) -> usize {
+            match actor {
+                Actor::Player => 0,
+                Actor::Monster(entity) => {
+                    1 + self
+                        .monsters
+                        .iter()
+                        .position(|&monster| monster == entity)
+                        .expect("Only monsters of the crowd act")
+                }
+            }
+        }
+    }
+
+    impl Actors for Crowd {
+        fn monsters(&self) -> Vec<Entity> {
+            self.monsters.clone()
+        }
+
+        fn energy_mut(&mut self, actor: Actor) -> &mut Energy {
+            let index = self.index(actor);
+            &mut self.energy[index]
+        }
+
+        fn speed(&self, actor: Actor) -> i32 {
+            self.speeds[self.index(actor)]
+        }
+    }
+
+    /// Runs the scheduler with every actor taking actions of `cost` whenever
+    /// it is handed one, and returns how many each took in `turns` whole
+    /// turns, counted from the end of the first
+    fn actions_over(turns: u32, speeds: &[i32], cost: i32) -> Vec<u32> {
+        let mut crowd = Crowd::new(speeds);
+        let mut scheduler = Scheduler::default();
+        let mut actions = vec![0; speeds.len()];
+        let mut turns_ended = 0;
+        while turns_ended <= turns {
+            match scheduler.next(&mut crowd) {
+                Next::Act(actor) => {
+                    crowd.energy_mut(actor).spend(cost);
+                    if turns_ended > 0 {
+                        actions[crowd.index(actor)] += 1;
+                    }
+                }
+                Next::TurnEnds => turns_ended += 1,
+            }
+        }
+        actions
+    }
+
+    #[test]
+    fn test_speed_sets_how_often_actors_act() {
+        let actions = actions_over(10, &[NORMAL_SPEED, 20, 8], ENERGY_TO_ACT);
+        assert_eq!(actions, vec![10, 20, 8]);
+    }
+
+    #[test]
+    fn test_double_speed_acts_twice_as_often() {
+        let actions = actions_over(50, &[20, NORMAL_SPEED], ActionKind::Move.cost());
+        assert_eq!(actions[0], actions[1] * 2);
+    }
+
+    #[test]
+    fn test_costly_actions_take_longer() {
+        let cast = ActionKind::Cast.cost();
+        let actions = actions_over(12, &[NORMAL_SPEED], cast);
+        assert_eq!(actions, vec![10]);
+    }
+
+    #[test]
+    fn test_player_goes_last_on_a_tie() {
+        let mut crowd = Crowd::new(&[NORMAL_SPEED, NORMAL_SPEED]);
+        crowd.energy = vec![Energy::ready(); 2];
+        let mut scheduler = Scheduler::default();
+
+        let monster = Actor::Monster(crowd.monsters[0]);
+        assert_eq!(scheduler.next(&mut crowd), Next::Act(monster));
+        crowd.energy_mut(monster).spend(ENERGY_TO_ACT);
+        assert_eq!(scheduler.next(&mut crowd), Next::Act(Actor::Player));
+        crowd.energy_mut(Actor::Player).spend(ENERGY_TO_ACT);
+        assert_eq!(scheduler.next(&mut crowd), Next::TurnEnds);
+    }
+
+    #[test]
+    fn test_ready_actor_acts_before_gaining() {
+        let mut energy = Energy::ready();
+        assert!(energy.can_act());
+        energy.spend(ActionKind::Move.cost());
+        assert!(!energy.can_act());
+        for _ in 0..TICKS_PER_TURN - 1 {
+            energy.gain(NORMAL_SPEED);
+        }
+        assert!(!energy.can_act());
+        energy.gain(NORMAL_SPEED);
+        assert!(energy.can_act());
+    }
+
+    #[test]
+    fn test_clock_completes_a_turn_every_few_ticks() {
+        let mut clock = TurnClock::default();
+        let turns: Vec<bool> = (0..TICKS_PER_TURN * 2).map(|_| clock.tick()).collect();
+        assert_eq!(turns.iter().filter(|&&done| done).count(), 2);
+        assert!(turns[TICKS_PER_TURN as usize - 1]);
+        assert!(turns[TICKS_PER_TURN as usize * 2 - 1]);
+    }
+
+    #[test]
+    fn test_haste_and_slow_change_speed() {
+        let mut status = StatusEffects::default();
+        assert_eq!(effective_speed(NORMAL_SPEED, &status), NORMAL_SPEED);
+        status.add(StatusKind::Hasted, 5);
+        assert_eq!(effective_speed(NORMAL_SPEED, &status), NORMAL_SPEED * 2);
+        status.add(StatusKind::Slowed, 5);
+        assert_eq!(effective_speed(NORMAL_SPEED, &status), NORMAL_SPEED);
+        status.remove(StatusKind::Hasted);
+        assert_eq!(effective_speed(NORMAL_SPEED, &status), NORMAL_SPEED / 2);
+    }
+}
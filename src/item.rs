@@ -0,0 +1,232 @@
+pub mod inventory;
+
+use crate::{
+    ecs::{Entities, Entity, Layer, Renderable},
+    entity::position::Position,
+};
+use rand::{Rng, seq::SliceRandom};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+pub use inventory::Inventory;
+
+/// Broad item categories, used for glyphs and for deciding what "use" means
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemCategory {
+    Potion,
+    Scroll,
+    Weapon,
+    Armour,
+    Talisman,
+    /// The goal of the run; cannot be used up
+    Artifact,
+    /// Opens a locked door
+    Key,
+}
+
+/// Every concrete item that can appear in the dungeon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemKind {
+    HealingPotion,
+    ScrollOfMapping,
+    ScrollOfTeleport,
+    /// 短刀 - short knife
+    Tanto,
+    /// 刀 - sword
+    Katana,
+    /// 羽織 - padded coat
+    Haori,
+    /// 鎧 - lamellar armour
+    Yoroi,
+    /// お札 - paper talisman that sears nearby yokai
+    Ofuda,
+    /// 百鬼夜行絵巻 - picture scroll of the night parade, carried by its leader
+    HyakkiEmaki,
+    /// 鍵 - unlocks a locked door, and is used up doing it
+    Key,
+}
+
+impl ItemKind {
+    /// Kinds that are generated at random; the artifact only comes from the
+    /// boss and keys only appear on floors with locked doors
+    pub const ALL: [ItemKind; 8] = [
+        ItemKind::HealingPotion,
+        ItemKind::ScrollOfMapping,
+        ItemKind::ScrollOfTeleport,
+        ItemKind::Tanto,
+        ItemKind::Katana,
+        ItemKind::Haori,
+        ItemKind::Yoroi,
+        ItemKind::Ofuda,
+    ];
+
+    pub fn category(&self) -> ItemCategory {
+        match self {
+            ItemKind::HealingPotion => ItemCategory::Potion,
+            ItemKind::ScrollOfMapping | ItemKind::ScrollOfTeleport => ItemCategory::Scroll,
+            ItemKind::Tanto | ItemKind::Katana => ItemCategory::Weapon,
+            ItemKind::Haori | ItemKind::Yoroi => ItemCategory::Armour,
+            ItemKind::Ofuda => ItemCategory::Talisman,
+            ItemKind::HyakkiEmaki => ItemCategory::Artifact,
+            ItemKind::Key => ItemCategory::Key,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ItemKind::HealingPotion => "Potion of Healing",
+            ItemKind::ScrollOfMapping => "Scroll of Mapping",
+            ItemKind::ScrollOfTeleport => "Scroll of Teleport",
+            ItemKind::Tanto => "Tanto",
+            ItemKind::Katana => "Katana",
+            ItemKind::Haori => "Haori",
+            ItemKind::Yoroi => "Yoroi",
+            ItemKind::Ofuda => "Ofuda",
+            ItemKind::HyakkiEmaki => "Hyakki Yagyō Emaki",
+            ItemKind::Key => "Key",
+        }
+    }
+
+    /// Attack bonus granted while equipped as a weapon
+    pub fn attack_bonus(&self) -> i32 {
+        match self {
+            ItemKind::Tanto => 1,
+            ItemKind::Katana => 3,
+            _ => 0,
+        }
+    }
+
+    /// Defense bonus granted while worn as armour
+    pub fn defense_bonus(&self) -> i32 {
+        match self {
+            ItemKind::Haori => 1,
+            ItemKind::Yoroi => 3,
+            _ => 0,
+        }
+    }
+
+    /// Shallowest depth at which this item can be generated
+    pub fn min_depth(&self) -> u32 {
+        match self {
+            ItemKind::Katana | ItemKind::Yoroi => 4,
+            _ => 1,
+        }
+    }
+
+    /// Picks a random item kind allowed at `depth`
+    pub fn random_for_depth<R: Rng>(depth: u32, rng: &mut R) -> ItemKind {
+        let candidates: Vec<ItemKind> = Self::ALL
+            .into_iter()
+            .filter(|kind| kind.min_depth() <= depth)
+            .collect();
+        *candidates
+            .choose(rng)
+            .expect("At least one item kind must be available at depth 1")
+    }
+}
+
+impl ItemCategory {
+    pub fn glyph(&self) -> char {
+        match self {
+            ItemCategory::Potion => '!',
+            ItemCategory::Scroll => '?',
+            ItemCategory::Weapon => ')',
+            ItemCategory::Armour => '[',
+            ItemCategory::Talisman => '~',
+            ItemCategory::Artifact => '&',
+            ItemCategory::Key => '-',
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            ItemCategory::Potion => Color::LightMagenta,
+            ItemCategory::Scroll => Color::White,
+            ItemCategory::Weapon => Color::LightCyan,
+            ItemCategory::Armour => Color::LightBlue,
+            ItemCategory::Talisman => Color::Yellow,
+            ItemCategory::Artifact => Color::LightYellow,
+            ItemCategory::Key => Color::LightRed,
+        }
+    }
+}
+
+/// A single item, either lying on the floor or carried by the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Item {
+    pub kind: ItemKind,
+}
+
+impl Item {
+    pub fn new(kind: ItemKind) -> Self {
+        Item { kind }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    pub fn category(&self) -> ItemCategory {
+        self.kind.category()
+    }
+
+    pub fn to_char(&self) -> char {
+        self.category().glyph()
+    }
+
+    pub fn color(&self) -> Color {
+        self.category().color()
+    }
+
+    /// Lays `item` on the floor at `position`
+    pub fn spawn(entities: &mut Entities, item: Item, position: Position) -> Entity {
+        let entity = entities.spawn();
+        entities.position.insert(entity, position);
+        entities.renderable.insert(
+            entity,
+            Renderable::new(item.to_char(), item.color(), Layer::Item),
+        );
+        entities.item.insert(entity, item);
+        entity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_item_glyph_follows_category() {
+        assert_eq!(Item::new(ItemKind::HealingPotion).to_char(), '!');
+        assert_eq!(Item::new(ItemKind::ScrollOfMapping).to_char(), '?');
+        assert_eq!(Item::new(ItemKind::Katana).to_char(), ')');
+        assert_eq!(Item::new(ItemKind::Yoroi).to_char(), '[');
+        assert_eq!(Item::new(ItemKind::Ofuda).to_char(), '~');
+        assert_eq!(Item::new(ItemKind::HyakkiEmaki).to_char(), '&');
+        assert_eq!(Item::new(ItemKind::Key).to_char(), '-');
+    }
+
+    #[test]
+    fn test_only_equipment_has_bonuses() {
+        for kind in ItemKind::ALL {
+            match kind.category() {
+                ItemCategory::Weapon => assert!(kind.attack_bonus() > 0),
+                ItemCategory::Armour => assert!(kind.defense_bonus() > 0),
+                _ => {
+                    assert_eq!(kind.attack_bonus(), 0);
+                    assert_eq!(kind.defense_bonus(), 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_item_respects_min_depth() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let kind = ItemKind::random_for_depth(1, &mut rng);
+            assert!(kind.min_depth() <= 1);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::item::{Item, ItemKind};
+use serde::{Deserialize, Serialize};
+
+/// A bounded list of carried items
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Inventory {
+    pub items: Vec<Item>,
+    pub capacity: usize,
+}
+
+impl Inventory {
+    pub fn new(capacity: usize) -> Self {
+        Inventory {
+            items: Vec::new(),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= self.capacity
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Item> {
+        self.items.get(index)
+    }
+
+    pub fn contains(&self, kind: ItemKind) -> bool {
+        self.items.iter().any(|item| item.kind == kind)
+    }
+
+    /// Adds an item, handing it back if the inventory is full
+    pub fn add(&mut self, item: Item) -> Result<(), Item> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Item> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_inventory_is_empty() {
+        let inventory = Inventory::new(3);
+        assert!(inventory.is_empty());
+        assert!(!inventory.is_full());
+    }
+
+    #[test]
+    fn test_add_until_full() {
+        let mut inventory = Inventory::new(2);
+        let potion = Item::new(ItemKind::HealingPotion);
+        assert!(inventory.add(potion).is_ok());
+        assert!(inventory.add(potion).is_ok());
+        assert!(inventory.is_full());
+        assert_eq!(inventory.add(potion), Err(potion));
+        assert_eq!(inventory.len(), 2);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut inventory = Inventory::new(2);
+        let katana = Item::new(ItemKind::Katana);
+        inventory.add(katana).unwrap();
+        assert_eq!(inventory.remove(1), None);
+        assert_eq!(inventory.remove(0), Some(katana));
+        assert!(inventory.is_empty());
+    }
+}
@@ -1,13 +1,30 @@
+pub mod cli;
+pub mod combat;
+pub mod ecs;
 pub mod entity;
 pub mod game;
+pub mod item;
+pub mod menu;
+pub mod message;
+pub mod options;
+pub mod save;
+pub mod score;
+pub mod spell;
 pub mod ui;
 pub mod world;
 
+pub use cli::Args;
 pub use game::Game;
 
 use anyhow::Result;
 use crossterm::event::{self, Event, KeyEventKind};
+use game::state::GameState;
+use menu::{MainMenu, MenuItem, MenuScreen};
+use message::MessageKind;
+use options::Options;
 use ratatui::DefaultTerminal;
+use score::HighScores;
+use std::path::{Path, PathBuf};
 use ui::{Action, handle_key, render};
 
 /// RAII guard to ensure terminal restoration on drop (including panics)
@@ -21,32 +38,364 @@ impl Drop for TerminalGuard {
     }
 }
 
-pub fn run() -> Result<()> {
-    let mut guard = TerminalGuard {
-        terminal: ratatui::init(),
-    };
-    run_game_loop(&mut guard.terminal)
+/// Files the game reads and writes; `None` when the platform has no data directory
+struct DataPaths {
+    save: Option<PathBuf>,
+    options: Option<PathBuf>,
+    scores: Option<PathBuf>,
+    /// Directory for morgue files of finished runs
+    morgue: Option<PathBuf>,
+}
+
+impl DataPaths {
+    fn new() -> Self {
+        DataPaths {
+            save: save::default_save_path(),
+            options: Options::default_path(),
+            scores: HighScores::default_path(),
+            morgue: save::default_morgue_dir(),
+        }
+    }
 }
 
-fn run_game_loop(terminal: &mut DefaultTerminal) -> Result<()> {
-    let mut game = Game::new();
+/// Runs the game, starting at the main menu
+///
+/// A saved run is resumed with "Continue", which consumes the save; it is
+/// only written back when the player quits alive, so a death is always final.
+/// Starting a new run from the menu instead asks first, since it abandons
+/// the saved one.
+/// A `--seed` skips the menu and starts a new run, which replaces the saved
+/// one when it is saved.
+pub fn run(args: Args) -> Result<()> {
+    let paths = DataPaths::new();
+    // Read files before taking over the terminal so errors print normally
+    let options = match &paths.options {
+        Some(path) => Options::load(path)?,
+        None => Options::default(),
+    };
+    let high_scores = match &paths.scores {
+        Some(path) => HighScores::load(path)?,
+        None => HighScores::default(),
+    };
+
+    let mut game = match args.seed {
+        Some(seed) => Game::with_seed(seed),
+        None => {
+            let has_save = paths.save.as_deref().is_some_and(Path::exists);
+            // The menu needs a `Game` to live in; it is replaced once a run starts
+            let mut game = Game::new();
+            game.menu = MainMenu::new(has_save, high_scores);
+            game.state = GameState::MainMenu;
+            game
+        }
+    };
+    game.options = options;
+
+    let game = {
+        let mut guard = TerminalGuard {
+            terminal: ratatui::init(),
+        };
+        run_game_loop(&mut guard.terminal, game, &paths)?
+    };
 
+    // Quitting from the main menu leaves any saved run untouched
+    if game.state == GameState::MainMenu {
+        return Ok(());
+    }
+    if let Some(path) = &paths.save {
+        if game.is_game_over() {
+            save::delete_save(path)?;
+        } else {
+            save::save_game(&game, path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs until the player quits, returning the final game state
+fn run_game_loop(
+    terminal: &mut DefaultTerminal,
+    mut game: Game,
+    paths: &DataPaths,
+) -> Result<Game> {
     while game.running {
         terminal.draw(|frame| render(frame, &game))?;
 
         match event::read()? {
-            Event::Key(key) if key.kind == KeyEventKind::Press => match handle_key(key) {
-                Action::Move { dx, dy } => game.try_move_player(dx, dy),
-                Action::Descend => game.try_descend(),
-                Action::Quit => game.quit(),
-                Action::None => {}
-            },
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let action = handle_key(key, game.state);
+                let was_over = game.is_game_over();
+                match game.state {
+                    GameState::MainMenu => handle_main_menu_action(&mut game, action, paths),
+                    GameState::ShowInventory => handle_inventory_action(&mut game, action),
+                    GameState::SelectSpell => handle_spell_menu_action(&mut game, action),
+                    GameState::Targeting => handle_targeting_action(&mut game, action),
+                    GameState::MessageHistory => handle_message_history_action(&mut game, action),
+                    GameState::CloseDoor => handle_close_door_action(&mut game, action),
+                    _ => handle_playing_action(&mut game, action),
+                }
+                if game.is_game_over() && !was_over {
+                    record_run(&mut game, paths);
+                }
+            }
             Event::Resize(_, _) => {
-                // Terminal resized, the next draw will handle it automatically
+                // The next draw resizes the buffer and re-centres the camera on the new size
             }
             _ => {}
         }
     }
 
-    Ok(())
+    Ok(game)
+}
+
+/// Writes the morgue file of a run that has just ended and enters it in the
+/// high score table
+///
+/// Failures are reported in the message log; they shouldn't take the
+/// game over screen down with them.
+fn record_run(game: &mut Game, paths: &DataPaths) {
+    if let Some(dir) = &paths.morgue {
+        match save::write_morgue(game, dir) {
+            Ok(path) => game.morgue_path = Some(path),
+            Err(e) => game.message(MessageKind::Warning, format!("{:#}", e)),
+        }
+    }
+    if let Some(path) = &paths.scores {
+        let ranked = HighScores::load(path).and_then(|mut scores| {
+            let rank = scores.insert(game.score_entry());
+            scores.save(path).map(|()| rank)
+        });
+        match ranked {
+            Ok(Some(rank)) => {
+                let text = format!("New high score: #{}!", rank + 1);
+                game.message(MessageKind::Good, text);
+            }
+            Ok(None) => {}
+            Err(e) => game.message(MessageKind::Warning, format!("{:#}", e)),
+        }
+    }
+}
+
+/// Applies an action on the main menu
+///
+/// Starting or continuing a run replaces `game` with that run.
+fn handle_main_menu_action(game: &mut Game, action: Action, paths: &DataPaths) {
+    match game.menu.screen {
+        MenuScreen::Title => match action {
+            Action::Move { dy, .. } => game.menu.move_cursor(dy),
+            Action::Confirm => match game.menu.selected() {
+                MenuItem::NewGame => new_run(game, None),
+                MenuItem::Continue => continue_run(game, paths.save.as_deref()),
+                MenuItem::SeededGame => game.menu.show(MenuScreen::SeedEntry),
+                MenuItem::HighScores => game.menu.show(MenuScreen::HighScores),
+                MenuItem::Options => game.menu.show(MenuScreen::Options),
+                MenuItem::Quit => game.quit(),
+            },
+            Action::Quit => game.quit(),
+            _ => {}
+        },
+        MenuScreen::SeedEntry => match action {
+            Action::Digit(digit) => game.menu.push_seed_digit(digit),
+            Action::Backspace => game.menu.pop_seed_digit(),
+            Action::Confirm => match game.menu.seed() {
+                Some(seed) => new_run(game, Some(seed)),
+                None => game.menu.error = Some(format!("Enter a number up to {}", u64::MAX)),
+            },
+            Action::Quit => game.menu.show(MenuScreen::Title),
+            _ => {}
+        },
+        MenuScreen::HighScores => {
+            if matches!(action, Action::Confirm | Action::Quit) {
+                game.menu.show(MenuScreen::Title);
+            }
+        }
+        MenuScreen::Options => match action {
+            Action::Confirm => {
+                game.options.auto_pickup = !game.options.auto_pickup;
+                if let Some(path) = &paths.options
+                    && let Err(e) = game.options.save(path)
+                {
+                    game.menu.error = Some(format!("{:#}", e));
+                }
+            }
+            Action::Quit => game.menu.show(MenuScreen::Title),
+            _ => {}
+        },
+        MenuScreen::ConfirmNewRun => match action {
+            Action::Confirm => start_new_run(game, game.menu.new_run_seed),
+            Action::Quit => game.menu.show(MenuScreen::Title),
+            _ => {}
+        },
+    }
+}
+
+/// Starts a new run, first asking for confirmation if it would replace a saved one
+fn new_run(game: &mut Game, seed: Option<u64>) {
+    if game.menu.confirm_new_run(seed) {
+        start_new_run(game, seed);
+    }
+}
+
+fn start_new_run(game: &mut Game, seed: Option<u64>) {
+    start_run(game, seed.map_or_else(Game::new, Game::with_seed));
+}
+
+/// Loads the saved run; if that fails the menu shows why and drops "Continue"
+fn continue_run(game: &mut Game, save_path: Option<&Path>) {
+    let loaded = match save_path {
+        Some(path) => save::load_game(path),
+        None => Ok(None),
+    };
+    match loaded {
+        Ok(Some(saved)) => start_run(game, saved),
+        Ok(None) => {
+            game.menu.forget_save();
+            game.menu.error = Some("There is no saved run.".to_string());
+        }
+        Err(e) => {
+            game.menu.forget_save();
+            game.menu.error = Some(format!("{:#}", e));
+        }
+    }
+}
+
+/// Replaces the menu's placeholder game with `run`, keeping the player's options
+fn start_run(game: &mut Game, mut run: Game) {
+    run.options = game.options;
+    *game = run;
+}
+
+/// Applies an action on the map; whenever it uses up the player's turn, the
+/// monsters act until the player is ready again
+fn handle_playing_action(game: &mut Game, action: Action) {
+    let turn_taken = match action {
+        Action::Quit => {
+            game.quit();
+            false
+        }
+        // Only quitting is possible once the player has died
+        _ if game.is_game_over() => false,
+        Action::Move { dx, dy } => game.try_move_player(dx, dy),
+        Action::Descend => game.try_descend(),
+        Action::Ascend => game.try_ascend(),
+        Action::PickUp => game.try_pick_up(),
+        Action::OpenInventory | Action::Drop | Action::Use => {
+            game.open_inventory();
+            false
+        }
+        Action::CastSpell => {
+            game.open_spell_menu();
+            false
+        }
+        Action::OpenMessages => {
+            game.open_message_history();
+            false
+        }
+        Action::CloseDoor => game.try_close_door(),
+        Action::Search => game.search(),
+        Action::Confirm | Action::Digit(_) | Action::Backspace | Action::None => false,
+    };
+
+    if turn_taken {
+        game.end_turn();
+    }
+}
+
+/// Applies an action while the inventory screen is open
+///
+/// Up/down move the selection, `u`/`d` use or drop the selected item and
+/// `i`/`q`/`Esc` close the screen.
+fn handle_inventory_action(game: &mut Game, action: Action) {
+    let index = game.inventory_cursor;
+    let turn_taken = match action {
+        Action::Move { dy, .. } => {
+            game.move_inventory_cursor(dy);
+            false
+        }
+        Action::Use => game.use_item(index),
+        Action::Drop => game.drop_item(index),
+        Action::OpenInventory | Action::Quit => {
+            game.close_inventory();
+            false
+        }
+        _ => false,
+    };
+
+    if turn_taken {
+        game.close_inventory();
+        game.end_turn();
+    }
+}
+
+/// Applies an action while the spell menu is open
+///
+/// Up/down move the selection, `z`/`u` choose the spell and `q`/`Esc` close the menu.
+fn handle_spell_menu_action(game: &mut Game, action: Action) {
+    let turn_taken = match action {
+        Action::Move { dy, .. } => {
+            game.move_spell_cursor(dy);
+            false
+        }
+        Action::CastSpell | Action::Use => game.select_spell(game.spell_cursor),
+        Action::Quit => {
+            game.close_spell_menu();
+            false
+        }
+        _ => false,
+    };
+
+    if turn_taken {
+        game.end_turn();
+    }
+}
+
+/// Applies an action while aiming a spell
+///
+/// Movement keys move the cursor, `z`/`u` cast and `q`/`Esc` cancel.
+fn handle_targeting_action(game: &mut Game, action: Action) {
+    let turn_taken = match action {
+        Action::Move { dx, dy } => {
+            game.move_target_cursor(dx, dy);
+            false
+        }
+        Action::CastSpell | Action::Use => game.cast_at_target(),
+        Action::Quit => {
+            game.cancel_targeting();
+            false
+        }
+        _ => false,
+    };
+
+    if turn_taken {
+        game.end_turn();
+    }
+}
+
+/// Applies an action while the message history is open
+///
+/// Up/down scroll through older messages and `m`/`q`/`Esc` close the screen.
+fn handle_message_history_action(game: &mut Game, action: Action) {
+    match action {
+        Action::Move { dy, .. } => game.scroll_message_history(dy),
+        Action::OpenMessages | Action::Quit => game.close_message_history(),
+        _ => {}
+    }
+}
+
+/// Applies an action while asked which door to close
+///
+/// A direction closes the door that way and `q`/`Esc` cancel.
+fn handle_close_door_action(game: &mut Game, action: Action) {
+    let turn_taken = match action {
+        Action::Move { dx, dy } => game.close_door_toward(dx, dy),
+        Action::Quit => {
+            game.cancel_close_door();
+            false
+        }
+        _ => false,
+    };
+
+    if turn_taken {
+        game.end_turn();
+    }
 }
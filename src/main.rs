@@ -1,5 +1,7 @@
 use anyhow::Result;
+use hyakki::Args;
 
 fn main() -> Result<()> {
-    hyakki::run()
+    let args = Args::parse(std::env::args().skip(1))?;
+    hyakki::run(args)
 }
@@ -0,0 +1,198 @@
+use crate::score::HighScores;
+
+/// Longest seed that can be typed (u64::MAX has 20 digits)
+const MAX_SEED_DIGITS: usize = 20;
+
+/// Entries on the title screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuItem {
+    NewGame,
+    /// Only offered when a saved run exists
+    Continue,
+    SeededGame,
+    HighScores,
+    Options,
+    Quit,
+}
+
+impl MenuItem {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MenuItem::NewGame => "New Game",
+            MenuItem::Continue => "Continue",
+            MenuItem::SeededGame => "Seeded Game",
+            MenuItem::HighScores => "High Scores",
+            MenuItem::Options => "Options",
+            MenuItem::Quit => "Quit",
+        }
+    }
+}
+
+/// Which page of the main menu is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MenuScreen {
+    #[default]
+    Title,
+    /// Typing the seed for a seeded game
+    SeedEntry,
+    HighScores,
+    Options,
+    /// Asking before a new run replaces the saved one
+    ConfirmNewRun,
+}
+
+/// State of the main menu shown while in `GameState::MainMenu`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MainMenu {
+    pub screen: MenuScreen,
+    /// Selected entry of `items()` on the title screen
+    pub cursor: usize,
+    /// Whether a saved run exists, which enables "Continue"
+    pub has_save: bool,
+    /// Digits typed on the seed entry page
+    pub seed_input: String,
+    /// Seed of the new run awaiting confirmation, `None` for a random one
+    pub new_run_seed: Option<u64>,
+    pub high_scores: HighScores,
+    /// Shown beneath the menu, e.g. when the save could not be loaded
+    pub error: Option<String>,
+}
+
+impl MainMenu {
+    pub fn new(has_save: bool, high_scores: HighScores) -> Self {
+        MainMenu {
+            has_save,
+            high_scores,
+            ..MainMenu::default()
+        }
+    }
+
+    /// Entries on the title screen, in display order
+    pub fn items(&self) -> Vec<MenuItem> {
+        let mut items = vec![MenuItem::NewGame];
+        if self.has_save {
+            items.push(MenuItem::Continue);
+        }
+        items.extend([
+            MenuItem::SeededGame,
+            MenuItem::HighScores,
+            MenuItem::Options,
+            MenuItem::Quit,
+        ]);
+        items
+    }
+
+    pub fn selected(&self) -> MenuItem {
+        self.items()[self.cursor]
+    }
+
+    /// Moves the title screen selection by `delta`, clamped to the entries
+    pub fn move_cursor(&mut self, delta: i32) {
+        let last = self.items().len() - 1;
+        self.cursor = self.cursor.saturating_add_signed(delta as isize).min(last);
+    }
+
+    /// Switches page, clearing any error from the previous one
+    pub fn show(&mut self, screen: MenuScreen) {
+        self.screen = screen;
+        self.error = None;
+        if screen == MenuScreen::SeedEntry {
+            self.seed_input.clear();
+        }
+    }
+
+    /// Whether a new run with `seed` can start right away; over a saved run
+    /// it switches to the confirmation page instead
+    ///
+    /// Both dying and quitting in the new run leave the saved one gone.
+    pub fn confirm_new_run(&mut self, seed: Option<u64>) -> bool {
+        if !self.has_save {
+            return true;
+        }
+        self.show(MenuScreen::ConfirmNewRun);
+        self.new_run_seed = seed;
+        false
+    }
+
+    /// Marks the save as gone (e.g. after a failed load), keeping the cursor valid
+    pub fn forget_save(&mut self) {
+        self.has_save = false;
+        self.move_cursor(0);
+    }
+
+    pub fn push_seed_digit(&mut self, digit: char) {
+        if digit.is_ascii_digit() && self.seed_input.len() < MAX_SEED_DIGITS {
+            self.seed_input.push(digit);
+        }
+    }
+
+    pub fn pop_seed_digit(&mut self) {
+        self.seed_input.pop();
+    }
+
+    /// The typed seed, or `None` if it is empty or too large for a `u64`
+    pub fn seed(&self) -> Option<u64> {
+        self.seed_input.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continue_only_with_save() {
+        assert!(
+            !MainMenu::new(false, HighScores::default())
+                .items()
+                .contains(&MenuItem::Continue)
+        );
+        let menu = MainMenu::new(true, HighScores::default());
+        assert_eq!(menu.items()[1], MenuItem::Continue);
+    }
+
+    #[test]
+    fn test_cursor_is_clamped() {
+        let mut menu = MainMenu::new(false, HighScores::default());
+        menu.move_cursor(-1);
+        assert_eq!(menu.selected(), MenuItem::NewGame);
+        menu.move_cursor(100);
+        assert_eq!(menu.selected(), MenuItem::Quit);
+    }
+
+    #[test]
+    fn test_forget_save_keeps_cursor_valid() {
+        let mut menu = MainMenu::new(true, HighScores::default());
+        menu.move_cursor(100);
+        menu.forget_save();
+        assert_eq!(menu.selected(), MenuItem::Quit);
+    }
+
+    #[test]
+    fn test_new_run_is_confirmed_only_over_a_save() {
+        let mut menu = MainMenu::new(false, HighScores::default());
+        assert!(menu.confirm_new_run(None));
+        assert_eq!(menu.screen, MenuScreen::Title);
+
+        let mut menu = MainMenu::new(true, HighScores::default());
+        assert!(!menu.confirm_new_run(Some(42)));
+        assert_eq!(menu.screen, MenuScreen::ConfirmNewRun);
+        assert_eq!(menu.new_run_seed, Some(42));
+    }
+
+    #[test]
+    fn test_seed_entry() {
+        let mut menu = MainMenu::new(false, HighScores::default());
+        menu.show(MenuScreen::SeedEntry);
+        assert_eq!(menu.seed(), None);
+
+        for digit in "1234x".chars() {
+            menu.push_seed_digit(digit);
+        }
+        menu.pop_seed_digit();
+        assert_eq!(menu.seed(), Some(123));
+
+        menu.seed_input = "99999999999999999999".to_string();
+        assert_eq!(menu.seed(), None);
+    }
+}
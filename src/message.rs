@@ -0,0 +1,110 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::{VecDeque, vec_deque};
+
+/// What a message is about, which decides its colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageKind {
+    /// Neutral information: movement, picking things up...
+    Info,
+    /// Something went well for the player: kills, healing...
+    Good,
+    /// Something hurt the player
+    Bad,
+    /// The action could not be done
+    Warning,
+}
+
+impl MessageKind {
+    pub fn color(&self) -> Color {
+        match self {
+            MessageKind::Info => Color::Gray,
+            MessageKind::Good => Color::LightGreen,
+            MessageKind::Bad => Color::LightRed,
+            MessageKind::Warning => Color::Yellow,
+        }
+    }
+}
+
+/// A line in the message log, stamped with the turn it happened on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    pub turn: u64,
+    pub kind: MessageKind,
+    pub text: String,
+}
+
+/// The history of messages shown to the player, oldest first
+///
+/// Only the most recent `capacity` messages are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageLog {
+    messages: VecDeque<Message>,
+    capacity: usize,
+}
+
+impl MessageLog {
+    pub fn new(capacity: usize) -> Self {
+        MessageLog {
+            messages: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Adds a message, forgetting the oldest one if the log is full
+    pub fn push(&mut self, turn: u64, kind: MessageKind, text: impl Into<String>) {
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(Message {
+            turn,
+            kind,
+            text: text.into(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// All messages, oldest first
+    pub fn iter(&self) -> vec_deque::Iter<'_, Message> {
+        self.messages.iter()
+    }
+
+    /// The most recent message, if any
+    pub fn last(&self) -> Option<&Message> {
+        self.messages.back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_keeps_order() {
+        let mut log = MessageLog::new(10);
+        log.push(1, MessageKind::Info, "first");
+        log.push(2, MessageKind::Bad, "second");
+
+        let texts: Vec<&str> = log.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["first", "second"]);
+        assert_eq!(log.last().map(|m| m.turn), Some(2));
+    }
+
+    #[test]
+    fn test_oldest_message_is_dropped_when_full() {
+        let mut log = MessageLog::new(2);
+        log.push(1, MessageKind::Info, "a");
+        log.push(2, MessageKind::Info, "b");
+        log.push(3, MessageKind::Info, "c");
+
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.iter().next().map(|m| m.text.as_str()), Some("b"));
+    }
+}
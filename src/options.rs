@@ -0,0 +1,30 @@
+use crate::save::{data_dir, read_json, write_json};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const OPTIONS_FILE_NAME: &str = "options.json";
+
+/// Player preferences, kept across runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Options {
+    /// Pick up items automatically when stepping onto them
+    pub auto_pickup: bool,
+}
+
+impl Options {
+    /// Default location: `options.json` in the data directory
+    pub fn default_path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(OPTIONS_FILE_NAME))
+    }
+
+    /// Loads the options at `path`; a missing file gives the defaults
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(read_json(path)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_json(path, self)
+    }
+}
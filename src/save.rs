@@ -0,0 +1,258 @@
+use crate::game::Game;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Version of the save file format
+///
+/// Bump this whenever a serialized type changes shape, so that old saves are
+/// rejected with a clear message instead of a confusing parse error.
+pub const SAVE_VERSION: u32 = 10;
+
+const DATA_DIR_NAME: &str = "hyakki";
+const SAVE_FILE_NAME: &str = "save.json";
+const MORGUE_DIR_NAME: &str = "morgue";
+
+/// On-disk layout: the version is checked before the game is parsed
+#[derive(Serialize, Deserialize)]
+struct SaveFile<G> {
+    version: u32,
+    game: G,
+}
+
+/// Only the header, used to check the version without parsing the whole game
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+/// Directory for everything the game stores: `$XDG_DATA_HOME/hyakki` (e.g. `~/.local/share`)
+///
+/// Returns `None` if the platform has no data directory, in which case
+/// nothing is saved.
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(DATA_DIR_NAME))
+}
+
+/// Default save location: `save.json` in [`data_dir`]
+pub fn default_save_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(SAVE_FILE_NAME))
+}
+
+/// Default morgue location: a `morgue` directory in [`data_dir`]
+pub fn default_morgue_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(MORGUE_DIR_NAME))
+}
+
+/// Writes the morgue file of a finished run into `dir`, returning its path
+///
+/// Files are named after the time the run ended (`morgue-1760000000.txt`),
+/// so every run keeps its own dump.
+pub fn write_morgue(game: &Game, dir: &Path) -> Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let path = dir.join(format!("morgue-{}.txt", timestamp));
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    fs::write(&path, game.morgue())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Writes the game to `path`, creating parent directories as needed
+pub fn save_game(game: &Game, path: &Path) -> Result<()> {
+    let save = SaveFile {
+        version: SAVE_VERSION,
+        game,
+    };
+    write_json(path, &save)
+}
+
+/// Loads and deletes the save at `path`
+///
+/// Returns `Ok(None)` when there is no save. Following roguelike permadeath
+/// rules the file is removed as soon as it has been read, even if it turns
+/// out to be corrupt, so a run can never be resumed twice.
+pub fn load_game(path: &Path) -> Result<Option<Game>> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read save file {}", path.display()));
+        }
+    };
+    delete_save(path)?;
+
+    let header: SaveHeader = serde_json::from_str(&json)
+        .with_context(|| format!("Save file {} is corrupt", path.display()))?;
+    if header.version != SAVE_VERSION {
+        bail!(
+            "Save file {} has version {}, but this build only supports version {}",
+            path.display(),
+            header.version,
+            SAVE_VERSION
+        );
+    }
+
+    let save: SaveFile<Game> = serde_json::from_str(&json)
+        .with_context(|| format!("Save file {} is corrupt", path.display()))?;
+    let mut game = save.game;
+    game.running = true;
+    Ok(Some(game))
+}
+
+/// Reads a JSON file such as the high score table; a missing file is `Ok(None)`
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    serde_json::from_str(&json)
+        .map(Some)
+        .with_context(|| format!("{} is corrupt", path.display()))
+}
+
+/// Writes `value` as JSON to `path`, creating parent directories as needed
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    let json = serde_json::to_string(value).context("Failed to serialize data")?;
+    fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Removes the save at `path`; a missing file is not an error
+pub fn delete_save(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to delete save file {}", path.display())),
+    }
+}
+
+/// Serializes `ratatui::style::Color` as a string (`"red"`, `"#ff8800"`...)
+///
+/// ratatui only implements serde behind a feature flag, but `Color` already
+/// round-trips through `Display`/`FromStr`, which is all a save file needs.
+pub(crate) mod color {
+    use ratatui::style::Color;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(color)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Color::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::state::GameState,
+        item::{Item, ItemKind},
+    };
+
+    /// A unique path in the system temp dir, so tests can run in parallel
+    fn temp_save_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("hyakki-test-{}-{}", std::process::id(), name))
+            .join(SAVE_FILE_NAME)
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = temp_save_path("round-trip");
+        let mut game = Game::new();
+        game.player_health_mut().hp = 7;
+        game.inventory_mut()
+            .add(Item::new(ItemKind::Katana))
+            .unwrap();
+        game.turn = 42;
+        game.running = false;
+
+        save_game(&game, &path).unwrap();
+        let loaded = load_game(&path).unwrap().expect("Save should exist");
+
+        assert!(loaded.running);
+        assert_eq!(loaded.state, GameState::Playing);
+        assert_eq!(loaded.turn, 42);
+        assert_eq!(loaded.seed, game.seed);
+        assert_eq!(loaded.messages, game.messages);
+        assert_eq!(loaded.player_health().hp, 7);
+        assert_eq!(loaded.player_pos(), game.player_pos());
+        assert_eq!(loaded.inventory(), game.inventory());
+        assert_eq!(loaded.dungeon.tiles, game.dungeon.tiles);
+        assert_eq!(loaded.dungeon.explored, game.dungeon.explored);
+        assert_eq!(loaded.entities, game.entities);
+    }
+
+    #[test]
+    fn test_load_deletes_save() {
+        let path = temp_save_path("permadeath");
+        save_game(&Game::new(), &path).unwrap();
+
+        assert!(load_game(&path).unwrap().is_some());
+        assert!(!path.exists());
+        assert!(load_game(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_missing_save_is_none() {
+        let path = temp_save_path("missing");
+        assert!(load_game(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_corrupt_save_is_error_and_deleted() {
+        let path = temp_save_path("corrupt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+
+        let Err(err) = load_game(&path) else {
+            panic!("Loading should fail");
+        };
+        assert!(err.to_string().contains("corrupt"), "{}", err);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_old_version_is_rejected() {
+        let path = temp_save_path("old-version");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"version":0,"game":{}}"#).unwrap();
+
+        let Err(err) = load_game(&path) else {
+            panic!("Loading should fail");
+        };
+        assert!(err.to_string().contains("version 0"), "{}", err);
+    }
+
+    #[test]
+    fn test_write_morgue() {
+        let dir = temp_save_path("morgue").with_file_name(MORGUE_DIR_NAME);
+        let game = Game::new();
+
+        let path = write_morgue(&game, &dir).unwrap();
+        assert!(path.starts_with(&dir));
+        assert_eq!(fs::read_to_string(&path).unwrap(), game.morgue());
+    }
+
+    #[test]
+    fn test_delete_missing_save_is_ok() {
+        let path = temp_save_path("delete-missing");
+        assert!(delete_save(&path).is_ok());
+    }
+}
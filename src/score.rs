@@ -0,0 +1,112 @@
+use crate::save::{data_dir, read_json, write_json};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Number of runs kept in the high score table
+pub const MAX_HIGH_SCORES: usize = 10;
+
+const SCORES_FILE_NAME: &str = "scores.json";
+
+/// A finished run in the high score table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub score: u64,
+    pub depth: u32,
+    pub turns: u64,
+    /// How the run ended, e.g. "Killed by an Oni"
+    pub cause: String,
+    pub seed: u64,
+}
+
+/// The best runs so far, highest score first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<ScoreEntry>,
+}
+
+impl HighScores {
+    /// Default location: `scores.json` in the data directory
+    pub fn default_path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(SCORES_FILE_NAME))
+    }
+
+    /// Loads the table at `path`; a missing file is an empty table
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(read_json(path)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_json(path, self)
+    }
+
+    /// Adds a run, keeping only the best `MAX_HIGH_SCORES`
+    ///
+    /// Returns the 0-based rank of the new entry, or `None` if it didn't make the table.
+    pub fn insert(&mut self, entry: ScoreEntry) -> Option<usize> {
+        // Ties go below existing entries, so earlier runs keep their place
+        let rank = self.entries.partition_point(|e| e.score >= entry.score);
+        if rank >= MAX_HIGH_SCORES {
+            return None;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u64) -> ScoreEntry {
+        ScoreEntry {
+            score,
+            depth: 1,
+            turns: 10,
+            cause: "Killed by a Kappa".to_string(),
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn test_insert_keeps_scores_sorted() {
+        let mut scores = HighScores::default();
+        assert_eq!(scores.insert(entry(50)), Some(0));
+        assert_eq!(scores.insert(entry(100)), Some(0));
+        assert_eq!(scores.insert(entry(75)), Some(1));
+
+        let values: Vec<u64> = scores.entries.iter().map(|e| e.score).collect();
+        assert_eq!(values, vec![100, 75, 50]);
+    }
+
+    #[test]
+    fn test_table_is_bounded() {
+        let mut scores = HighScores::default();
+        for score in 1..=MAX_HIGH_SCORES as u64 {
+            scores.insert(entry(score * 10));
+        }
+        assert_eq!(scores.insert(entry(5)), None);
+        assert_eq!(scores.insert(entry(15)), Some(MAX_HIGH_SCORES - 1));
+        assert_eq!(scores.entries.len(), MAX_HIGH_SCORES);
+        assert_eq!(scores.entries.last().map(|e| e.score), Some(15));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("hyakki-test-{}-scores", std::process::id()))
+            .join(SCORES_FILE_NAME);
+        let mut scores = HighScores::default();
+        scores.insert(entry(42));
+
+        scores.save(&path).unwrap();
+        assert_eq!(HighScores::load(&path).unwrap(), scores);
+    }
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let path = std::env::temp_dir().join("hyakki-test-no-such-scores.json");
+        assert!(HighScores::load(&path).unwrap().entries.is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::entity::position::Position;
+use serde::{Deserialize, Serialize};
+
+/// Spells the player can cast with mana
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Spell {
+    /// 狐火 - a bolt of foxfire that strikes the first thing in its path
+    Bolt,
+    /// 爆符 - an exploding talisman that damages everything around the target
+    Blast,
+    /// 縮地 - instantly step to a visible tile
+    Blink,
+    /// 治癒 - restore HP
+    Heal,
+    /// 千里眼 - reveal the layout of the whole floor
+    Reveal,
+    /// 韋駄天 - act twice as often for a while
+    Haste,
+}
+
+/// How a spell chooses where it takes effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellTarget {
+    /// Affects the caster; cast immediately
+    Caster,
+    /// Aimed at a tile within `range` using the targeting cursor
+    Tile { range: i32 },
+}
+
+impl Spell {
+    pub const ALL: [Spell; 6] = [
+        Spell::Bolt,
+        Spell::Blast,
+        Spell::Blink,
+        Spell::Heal,
+        Spell::Reveal,
+        Spell::Haste,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Spell::Bolt => "Foxfire Bolt",
+            Spell::Blast => "Blast Talisman",
+            Spell::Blink => "Blink",
+            Spell::Heal => "Heal",
+            Spell::Reveal => "Clairvoyance",
+            Spell::Haste => "Idaten's Stride",
+        }
+    }
+
+    pub fn mana_cost(&self) -> i32 {
+        match self {
+            Spell::Bolt => 3,
+            Spell::Blast => 6,
+            Spell::Blink => 4,
+            Spell::Heal => 5,
+            Spell::Reveal => 8,
+            Spell::Haste => 7,
+        }
+    }
+
+    pub fn target(&self) -> SpellTarget {
+        match self {
+            Spell::Bolt => SpellTarget::Tile { range: 8 },
+            Spell::Blast => SpellTarget::Tile { range: 6 },
+            Spell::Blink => SpellTarget::Tile { range: 6 },
+            Spell::Heal | Spell::Reveal | Spell::Haste => SpellTarget::Caster,
+        }
+    }
+
+    /// Damage dealt to each monster hit (0 for non-damaging spells)
+    pub fn damage(&self) -> i32 {
+        match self {
+            Spell::Bolt => 7,
+            Spell::Blast => 5,
+            _ => 0,
+        }
+    }
+}
+
+/// The spells a caster knows and the mana used to cast them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpellBook {
+    pub spells: Vec<Spell>,
+    pub mana: i32,
+    pub max_mana: i32,
+}
+
+impl SpellBook {
+    /// Creates a spell book with full mana
+    pub fn new(spells: Vec<Spell>, max_mana: i32) -> Self {
+        SpellBook {
+            spells,
+            mana: max_mana,
+            max_mana,
+        }
+    }
+
+    pub fn can_cast(&self, spell: Spell) -> bool {
+        self.mana >= spell.mana_cost()
+    }
+
+    /// Deducts the spell's mana cost, returning `false` if there isn't enough mana
+    pub fn spend(&mut self, spell: Spell) -> bool {
+        if !self.can_cast(spell) {
+            return false;
+        }
+        self.mana -= spell.mana_cost();
+        true
+    }
+
+    pub fn restore(&mut self, amount: i32) {
+        self.mana = (self.mana + amount).min(self.max_mana);
+    }
+}
+
+/// An in-progress aimed spell: the spell and where the cursor currently is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Targeting {
+    pub spell: Spell,
+    pub cursor: Position,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_book_starts_full() {
+        let book = SpellBook::new(Spell::ALL.to_vec(), 20);
+        assert_eq!(book.mana, 20);
+        assert_eq!(book.spells.len(), Spell::ALL.len());
+    }
+
+    #[test]
+    fn test_spend_mana() {
+        let mut book = SpellBook::new(vec![Spell::Reveal], 10);
+        assert!(book.spend(Spell::Reveal));
+        assert_eq!(book.mana, 2);
+        assert!(!book.spend(Spell::Reveal));
+        assert_eq!(book.mana, 2);
+    }
+
+    #[test]
+    fn test_restore_is_capped() {
+        let mut book = SpellBook::new(vec![], 10);
+        book.mana = 8;
+        book.restore(5);
+        assert_eq!(book.mana, 10);
+    }
+
+    #[test]
+    fn test_only_attack_spells_deal_damage() {
+        for spell in Spell::ALL {
+            let expected = matches!(spell, Spell::Bolt | Spell::Blast);
+            assert_eq!(spell.damage() > 0, expected, "{:?}", spell);
+        }
+    }
+}
@@ -1,4 +1,8 @@
+pub mod camera;
+pub mod game_over;
+pub mod hud;
 pub mod input;
+pub mod main_menu;
 pub mod renderer;
 
 pub use input::{Action, handle_key};
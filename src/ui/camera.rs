@@ -0,0 +1,106 @@
+use crate::entity::position::Position;
+
+/// The window of the map that fits on screen, centred on the player
+///
+/// Near the map edges the window stops scrolling instead of showing space
+/// past the edge, so the player is only off-centre there:
+///
+/// ```text
+/// map:  ##########################
+///       #.....@..........#.......#      view width 10
+///       ##########################
+///       [        ]                      origin.x = 0 (clamped)
+///            [     @    ]               origin.x = focus.x - 5
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Camera {
+    /// Map position drawn at the top-left corner of the view
+    pub origin: Position,
+    /// Number of map columns shown (never more than the map has)
+    pub width: i32,
+    /// Number of map rows shown (never more than the map has)
+    pub height: i32,
+}
+
+impl Camera {
+    /// Centres a `view_width x view_height` window on `focus`
+    pub fn new(
+        focus: Position,
+        view_width: u16,
+        view_height: u16,
+        map_width: usize,
+        map_height: usize,
+    ) -> Self {
+        let width = (view_width as i32).min(map_width as i32);
+        let height = (view_height as i32).min(map_height as i32);
+        Camera {
+            origin: Position {
+                x: axis_origin(focus.x, width, map_width as i32),
+                y: axis_origin(focus.y, height, map_height as i32),
+            },
+            width,
+            height,
+        }
+    }
+
+    /// Map position shown at column `dx`, row `dy` of the view
+    pub fn to_map(&self, dx: i32, dy: i32) -> Position {
+        self.origin.translate(dx, dy)
+    }
+}
+
+/// First map coordinate on one axis: centred on `focus`, clamped to the map
+fn axis_origin(focus: i32, view: i32, map: i32) -> i32 {
+    (focus - view / 2).clamp(0, map - view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP_WIDTH: usize = 80;
+    const MAP_HEIGHT: usize = 50;
+
+    fn camera(x: i32, y: i32, view_width: u16, view_height: u16) -> Camera {
+        Camera::new(
+            Position { x, y },
+            view_width,
+            view_height,
+            MAP_WIDTH,
+            MAP_HEIGHT,
+        )
+    }
+
+    #[test]
+    fn test_centres_on_focus() {
+        let camera = camera(40, 25, 20, 10);
+        assert_eq!(camera.origin, Position { x: 30, y: 20 });
+        assert_eq!(camera.to_map(10, 5), Position { x: 40, y: 25 });
+    }
+
+    #[test]
+    fn test_clamped_at_top_left() {
+        let camera = camera(2, 1, 20, 10);
+        assert_eq!(camera.origin, Position { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn test_clamped_at_bottom_right() {
+        let camera = camera(79, 49, 20, 10);
+        assert_eq!(camera.origin, Position { x: 60, y: 40 });
+        assert_eq!(camera.to_map(19, 9), Position { x: 79, y: 49 });
+    }
+
+    #[test]
+    fn test_view_larger_than_map_shows_whole_map() {
+        let camera = camera(70, 40, 200, 100);
+        assert_eq!(camera.origin, Position { x: 0, y: 0 });
+        assert_eq!((camera.width, camera.height), (80, 50));
+    }
+
+    #[test]
+    fn test_empty_view() {
+        let camera = camera(40, 25, 0, 0);
+        assert_eq!((camera.width, camera.height), (0, 0));
+    }
+}
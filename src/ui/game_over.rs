@@ -0,0 +1,66 @@
+use crate::{game::Game, ui::renderer::centered_rect};
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// End-of-run popup size (including borders)
+const GAME_OVER_WIDTH: u16 = 56;
+const GAME_OVER_HEIGHT: u16 = 14;
+
+/// Draws the summary of a finished run, won or lost, over the final map
+///
+/// ```text
+/// ┌Game Over──────────────────────┐
+/// │       Killed by an Oni        │
+/// │                               │
+/// │ Depth reached   3             │
+/// │ Turns           812           │
+/// │ ...                           │
+/// └q: quit────────────────────────┘
+/// ```
+pub fn render_game_over(frame: &mut Frame, game: &Game) {
+    let (title, color) = if game.has_won() {
+        ("Victory", Color::LightGreen)
+    } else {
+        ("Game Over", Color::LightRed)
+    };
+    let row = |label: &str, value: String| Line::from(format!(" {:<15} {}", label, value));
+    let mut lines = vec![
+        Line::styled(
+            game.end_cause(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center),
+        Line::from(""),
+        row("Depth reached", game.deepest_depth().to_string()),
+        row("Turns", game.turn.to_string()),
+        row("Kills", game.kills.to_string()),
+        row("Level", game.player().level.to_string()),
+        row("Gold", format!("{} mon", game.player().gold)),
+        row("Score", game.score().to_string()),
+    ];
+    if let Some(path) = &game.morgue_path {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            format!(" Morgue file: {}", path.display()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let area = centered_rect(frame.area(), GAME_OVER_WIDTH, GAME_OVER_HEIGHT);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_bottom("q: quit");
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
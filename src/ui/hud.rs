@@ -0,0 +1,174 @@
+use crate::{
+    entity::status::StatusKind,
+    game::{Game, config::MAX_DEPTH},
+};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+/// Width of the HP/MP bars in the side panel
+const BAR_WIDTH: usize = 14;
+
+/// Full status panel, shown beside the map on wide terminals
+///
+/// ```text
+/// ┌Status──────────────────┐
+/// │Depth  3 / 10           │
+/// │Turn   123              │
+/// │                        │
+/// │HP ██████████░░░░ 22/30 │
+/// │MP ████████░░░░░░ 12/20 │
+/// │...                     │
+/// ```
+pub fn render_status_panel(frame: &mut Frame, area: Rect, game: &Game) {
+    let player = game.player();
+    let health = game.player_health();
+    let stats = game.player_stats();
+    let book = &player.spellbook;
+
+    let mut lines = vec![
+        Line::from(format!("Depth  {} / {}", game.dungeon.depth, MAX_DEPTH)),
+        Line::from(format!("Turn   {}", game.turn)),
+        Line::from(""),
+        gauge_line("HP", health.hp, health.max_hp, hp_color(game)),
+        gauge_line("MP", book.mana, book.max_mana, Color::LightBlue),
+        Line::from(""),
+        Line::from(format!(
+            "Level  {}  ({}/{} XP)",
+            player.level,
+            player.xp,
+            player.xp_to_next_level()
+        )),
+        Line::from(vec![
+            Span::raw("Gold   "),
+            Span::styled(
+                format!("{} mon", player.gold),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Line::from(format!("Atk    {}   Def {}", stats.attack, stats.defense)),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Status",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    if player.status.effects.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  (none)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for effect in &player.status.effects {
+        lines.push(Line::from(Span::styled(
+            format!("  {} ({})", effect.kind.name(), effect.turns_left),
+            Style::default().fg(status_color(effect.kind)),
+        )));
+    }
+
+    let block = Block::default().borders(Borders::ALL).title("Status");
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// One-line status bar, shown beneath the map on narrow terminals
+///
+/// ```text
+/// D 3/10 | HP 22/30 | MP 12/20 | Lv 2 | 25 mon | T 123 | Regenerating
+/// ```
+pub fn render_status_bar(frame: &mut Frame, area: Rect, game: &Game) {
+    let player = game.player();
+    let health = game.player_health();
+    let book = &player.spellbook;
+    let separator = || Span::styled(" | ", Style::default().fg(Color::DarkGray));
+
+    let mut spans = vec![
+        Span::raw(format!("D {}/{}", game.dungeon.depth, MAX_DEPTH)),
+        separator(),
+        Span::styled(
+            format!("HP {}/{}", health.hp, health.max_hp),
+            Style::default().fg(hp_color(game)),
+        ),
+        separator(),
+        Span::styled(
+            format!("MP {}/{}", book.mana, book.max_mana),
+            Style::default().fg(Color::LightBlue),
+        ),
+        separator(),
+        Span::raw(format!("Lv {}", player.level)),
+        separator(),
+        Span::styled(
+            format!("{} mon", player.gold),
+            Style::default().fg(Color::Yellow),
+        ),
+        separator(),
+        Span::raw(format!("T {}", game.turn)),
+    ];
+    for effect in &player.status.effects {
+        spans.push(separator());
+        spans.push(Span::styled(
+            effect.kind.name(),
+            Style::default().fg(status_color(effect.kind)),
+        ));
+    }
+
+    let block = Block::default().borders(Borders::ALL);
+    frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+}
+
+fn status_color(kind: StatusKind) -> Color {
+    if kind.is_harmful() {
+        Color::LightRed
+    } else {
+        Color::LightGreen
+    }
+}
+
+/// `HP ██████████░░░░ 22/30`
+fn gauge_line(label: &str, current: i32, max: i32, color: Color) -> Line<'static> {
+    Line::from(vec![
+        Span::raw(format!("{} ", label)),
+        Span::styled(bar(current, max, BAR_WIDTH), Style::default().fg(color)),
+        Span::raw(format!(" {}/{}", current, max)),
+    ])
+}
+
+/// A `width` character bar filled in proportion to `current / max`
+fn bar(current: i32, max: i32, width: usize) -> String {
+    let filled = if max > 0 {
+        (current.clamp(0, max) as usize * width).div_ceil(max as usize)
+    } else {
+        0
+    };
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// Green when healthy, yellow when hurt, red when close to death
+fn hp_color(game: &Game) -> Color {
+    let health = game.player_health();
+    if health.hp * 4 <= health.max_hp {
+        Color::LightRed
+    } else if health.hp * 2 <= health.max_hp {
+        Color::Yellow
+    } else {
+        Color::LightGreen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_fill() {
+        assert_eq!(bar(10, 10, 4), "████");
+        assert_eq!(bar(0, 10, 4), "░░░░");
+        assert_eq!(bar(5, 10, 4), "██░░");
+        // Any HP left shows at least one block
+        assert_eq!(bar(1, 30, 4), "█░░░");
+        assert_eq!(bar(-3, 10, 4), "░░░░");
+    }
+}
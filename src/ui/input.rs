@@ -1,27 +1,92 @@
+use crate::game::state::GameState;
 use crossterm::event::{KeyCode, KeyEvent};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
-    Move { dx: i32, dy: i32 },
+    Move {
+        dx: i32,
+        dy: i32,
+    },
     Descend,
+    Ascend,
+    PickUp,
+    Drop,
+    Use,
+    OpenInventory,
+    CastSpell,
+    OpenMessages,
+    CloseDoor,
+    /// Look for hidden things around the player
+    Search,
+    /// Choose the selected menu entry (Enter)
+    Confirm,
+    /// A digit typed into a text field, e.g. the seed
+    Digit(char),
+    /// Delete the last typed character
+    Backspace,
     Quit,
     None,
 }
 
-pub fn handle_key(key: KeyEvent) -> Action {
+/// Maps a key to an action using the keymap of the current screen
+pub fn handle_key(key: KeyEvent, state: GameState) -> Action {
+    match state {
+        GameState::MainMenu => main_menu_key(key),
+        GameState::Playing => playing_key(key),
+        GameState::ShowInventory => inventory_key(key),
+        GameState::SelectSpell | GameState::Targeting => spell_key(key),
+        GameState::MessageHistory => message_history_key(key),
+        GameState::CloseDoor => direction_prompt_key(key),
+        GameState::GameOver => game_over_key(key),
+    }
+}
+
+/// Arrow and vi-style movement keys, shared by every screen
+fn direction_key(key: KeyEvent) -> Option<Action> {
+    let (dx, dy) = match key.code {
+        KeyCode::Up | KeyCode::Char('k') => (0, -1),
+        KeyCode::Down | KeyCode::Char('j') => (0, 1),
+        KeyCode::Left | KeyCode::Char('h') => (-1, 0),
+        KeyCode::Right | KeyCode::Char('l') => (1, 0),
+        _ => return None,
+    };
+    Some(Action::Move { dx, dy })
+}
+
+fn main_menu_key(key: KeyEvent) -> Action {
+    if let Some(action) = direction_key(key) {
+        return action;
+    }
+    match key.code {
+        KeyCode::Enter => Action::Confirm,
+        KeyCode::Char(c) if c.is_ascii_digit() => Action::Digit(c),
+        KeyCode::Backspace => Action::Backspace,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => Action::None,
+    }
+}
+
+fn playing_key(key: KeyEvent) -> Action {
+    if let Some(action) = direction_key(key) {
+        return action;
+    }
     match key.code {
-        // Arrow keys
-        KeyCode::Up => Action::Move { dx: 0, dy: -1 },
-        KeyCode::Down => Action::Move { dx: 0, dy: 1 },
-        KeyCode::Left => Action::Move { dx: -1, dy: 0 },
-        KeyCode::Right => Action::Move { dx: 1, dy: 0 },
-        // Vi-style keys
-        KeyCode::Char('h') => Action::Move { dx: -1, dy: 0 },
-        KeyCode::Char('j') => Action::Move { dx: 0, dy: 1 },
-        KeyCode::Char('k') => Action::Move { dx: 0, dy: -1 },
-        KeyCode::Char('l') => Action::Move { dx: 1, dy: 0 },
-        // Descend stairs
+        // Stairs
         KeyCode::Char('>') => Action::Descend,
+        KeyCode::Char('<') => Action::Ascend,
+        // Items
+        KeyCode::Char('g') | KeyCode::Char(',') => Action::PickUp,
+        KeyCode::Char('d') => Action::Drop,
+        KeyCode::Char('u') => Action::Use,
+        KeyCode::Char('i') => Action::OpenInventory,
+        // Spells
+        KeyCode::Char('z') => Action::CastSpell,
+        // Message history
+        KeyCode::Char('m') => Action::OpenMessages,
+        // Doors
+        KeyCode::Char('c') => Action::CloseDoor,
+        // Traps
+        KeyCode::Char('s') => Action::Search,
         // Quit
         KeyCode::Char('q') => Action::Quit,
         KeyCode::Esc => Action::Quit,
@@ -29,6 +94,60 @@ pub fn handle_key(key: KeyEvent) -> Action {
     }
 }
 
+fn inventory_key(key: KeyEvent) -> Action {
+    if let Some(action) = direction_key(key) {
+        return action;
+    }
+    match key.code {
+        KeyCode::Char('u') | KeyCode::Enter => Action::Use,
+        KeyCode::Char('d') => Action::Drop,
+        KeyCode::Char('i') => Action::OpenInventory,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => Action::None,
+    }
+}
+
+/// Keys for the spell menu and for aiming a spell
+fn spell_key(key: KeyEvent) -> Action {
+    if let Some(action) = direction_key(key) {
+        return action;
+    }
+    match key.code {
+        KeyCode::Char('z') | KeyCode::Char('u') | KeyCode::Enter => Action::CastSpell,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => Action::None,
+    }
+}
+
+fn message_history_key(key: KeyEvent) -> Action {
+    if let Some(action) = direction_key(key) {
+        return action;
+    }
+    match key.code {
+        KeyCode::Char('m') => Action::OpenMessages,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => Action::None,
+    }
+}
+
+/// Keys while asked for a direction: a direction, or `q`/`Esc` to cancel
+fn direction_prompt_key(key: KeyEvent) -> Action {
+    if let Some(action) = direction_key(key) {
+        return action;
+    }
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => Action::None,
+    }
+}
+
+fn game_over_key(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => Action::Quit,
+        _ => Action::None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,19 +165,19 @@ mod tests {
     #[test]
     fn test_arrow_keys() {
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Up)),
+            handle_key(make_key_event(KeyCode::Up), GameState::Playing),
             Action::Move { dx: 0, dy: -1 }
         );
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Down)),
+            handle_key(make_key_event(KeyCode::Down), GameState::Playing),
             Action::Move { dx: 0, dy: 1 }
         );
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Left)),
+            handle_key(make_key_event(KeyCode::Left), GameState::Playing),
             Action::Move { dx: -1, dy: 0 }
         );
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Right)),
+            handle_key(make_key_event(KeyCode::Right), GameState::Playing),
             Action::Move { dx: 1, dy: 0 }
         );
     }
@@ -66,41 +185,146 @@ mod tests {
     #[test]
     fn test_vi_keys() {
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Char('h'))),
+            handle_key(make_key_event(KeyCode::Char('h')), GameState::Playing),
             Action::Move { dx: -1, dy: 0 }
         );
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Char('j'))),
+            handle_key(make_key_event(KeyCode::Char('j')), GameState::Playing),
             Action::Move { dx: 0, dy: 1 }
         );
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Char('k'))),
+            handle_key(make_key_event(KeyCode::Char('k')), GameState::Playing),
             Action::Move { dx: 0, dy: -1 }
         );
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Char('l'))),
+            handle_key(make_key_event(KeyCode::Char('l')), GameState::Playing),
             Action::Move { dx: 1, dy: 0 }
         );
     }
 
     #[test]
     fn test_quit_keys() {
-        assert_eq!(handle_key(make_key_event(KeyCode::Char('q'))), Action::Quit);
-        assert_eq!(handle_key(make_key_event(KeyCode::Esc)), Action::Quit);
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('q')), GameState::Playing),
+            Action::Quit
+        );
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Esc), GameState::Playing),
+            Action::Quit
+        );
     }
 
     #[test]
     fn test_descend_key() {
         assert_eq!(
-            handle_key(make_key_event(KeyCode::Char('>'))),
+            handle_key(make_key_event(KeyCode::Char('>')), GameState::Playing),
             Action::Descend
         );
     }
 
+    #[test]
+    fn test_ascend_key() {
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('<')), GameState::Playing),
+            Action::Ascend
+        );
+    }
+
+    #[test]
+    fn test_item_keys() {
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('g')), GameState::Playing),
+            Action::PickUp
+        );
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char(',')), GameState::Playing),
+            Action::PickUp
+        );
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('d')), GameState::Playing),
+            Action::Drop
+        );
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('u')), GameState::Playing),
+            Action::Use
+        );
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('i')), GameState::Playing),
+            Action::OpenInventory
+        );
+    }
+
+    #[test]
+    fn test_cast_spell_key() {
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('z')), GameState::Playing),
+            Action::CastSpell
+        );
+    }
+
+    #[test]
+    fn test_message_history_key() {
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('m')), GameState::Playing),
+            Action::OpenMessages
+        );
+    }
+
+    #[test]
+    fn test_close_door_keys() {
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('c')), GameState::Playing),
+            Action::CloseDoor
+        );
+        let key = |code| handle_key(make_key_event(code), GameState::CloseDoor);
+        assert_eq!(key(KeyCode::Char('h')), Action::Move { dx: -1, dy: 0 });
+        assert_eq!(key(KeyCode::Esc), Action::Quit);
+        assert_eq!(key(KeyCode::Char('c')), Action::None);
+    }
+
+    #[test]
+    fn test_search_key() {
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('s')), GameState::Playing),
+            Action::Search
+        );
+    }
+
+    #[test]
+    fn test_main_menu_keys() {
+        let key = |code| handle_key(make_key_event(code), GameState::MainMenu);
+        assert_eq!(key(KeyCode::Enter), Action::Confirm);
+        assert_eq!(key(KeyCode::Char('7')), Action::Digit('7'));
+        assert_eq!(key(KeyCode::Backspace), Action::Backspace);
+        assert_eq!(key(KeyCode::Char('j')), Action::Move { dx: 0, dy: 1 });
+        assert_eq!(key(KeyCode::Char('z')), Action::None);
+    }
+
+    #[test]
+    fn test_keymap_depends_on_state() {
+        let enter = make_key_event(KeyCode::Enter);
+        assert_eq!(handle_key(enter, GameState::Playing), Action::None);
+        assert_eq!(handle_key(enter, GameState::ShowInventory), Action::Use);
+        assert_eq!(handle_key(enter, GameState::Targeting), Action::CastSpell);
+
+        let descend = make_key_event(KeyCode::Char('>'));
+        assert_eq!(handle_key(descend, GameState::GameOver), Action::None);
+        assert_eq!(handle_key(descend, GameState::ShowInventory), Action::None);
+    }
+
     #[test]
     fn test_unknown_keys() {
-        assert_eq!(handle_key(make_key_event(KeyCode::Char('x'))), Action::None);
-        assert_eq!(handle_key(make_key_event(KeyCode::Enter)), Action::None);
-        assert_eq!(handle_key(make_key_event(KeyCode::Tab)), Action::None);
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Char('x')), GameState::Playing),
+            Action::None
+        );
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Enter), GameState::Playing),
+            Action::None
+        );
+        assert_eq!(
+            handle_key(make_key_event(KeyCode::Tab), GameState::Playing),
+            Action::None
+        );
     }
 }
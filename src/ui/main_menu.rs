@@ -0,0 +1,154 @@
+use crate::{
+    game::Game,
+    menu::{MainMenu, MenuScreen},
+    options::Options,
+    ui::renderer::centered_rect,
+};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+
+const LOGO: [&str; 5] = [
+    "█  █ █   █  ██  █  █ █  █ ███",
+    "█  █  █ █  █  █ █ █  █ █   █ ",
+    "████   █   ████ ██   ██    █ ",
+    "█  █   █   █  █ █ █  █ █   █ ",
+    "█  █   █   █  █ █  █ █  █ ███",
+];
+
+/// Size of the box holding the current menu page (including borders)
+const MENU_WIDTH: u16 = 60;
+const MENU_HEIGHT: u16 = 14;
+
+/// Draws the title screen: the logo above the current menu page
+pub fn render_main_menu(frame: &mut Frame, game: &Game) {
+    let menu = &game.menu;
+    let [logo_area, menu_area] = Layout::vertical([
+        Constraint::Length(LOGO.len() as u16 + 3),
+        Constraint::Length(MENU_HEIGHT),
+    ])
+    .flex(Flex::Center)
+    .areas(frame.area());
+
+    let mut logo: Vec<Line> = LOGO
+        .iter()
+        .map(|row| Line::styled(*row, Style::default().fg(Color::LightRed)))
+        .collect();
+    logo.push(Line::from(""));
+    logo.push(Line::styled(
+        "百鬼夜行 - the night parade of a hundred demons",
+        Style::default().fg(Color::Gray),
+    ));
+    frame.render_widget(Paragraph::new(logo).alignment(Alignment::Center), logo_area);
+
+    let (title, hints, mut lines) = match menu.screen {
+        MenuScreen::Title => ("", "↑/↓: select  Enter: choose  q: quit", title_lines(menu)),
+        MenuScreen::SeedEntry => ("Seeded Game", "Enter: start  Esc: back", seed_lines(menu)),
+        MenuScreen::HighScores => ("High Scores", "Esc: back", high_score_lines(menu)),
+        MenuScreen::Options => (
+            "Options",
+            "Enter: toggle  Esc: back",
+            option_lines(&game.options),
+        ),
+        MenuScreen::ConfirmNewRun => (
+            "New Game",
+            "Enter: start anyway  Esc: back",
+            confirm_new_run_lines(),
+        ),
+    };
+    if let Some(error) = &menu.error {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            error.clone(),
+            Style::default().fg(Color::LightRed),
+        ));
+    }
+
+    let area = centered_rect(menu_area, MENU_WIDTH, MENU_HEIGHT);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_bottom(hints);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center),
+        area,
+    );
+}
+
+fn title_lines(menu: &MainMenu) -> Vec<Line<'static>> {
+    menu.items()
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            if i == menu.cursor {
+                Line::styled(
+                    format!("> {} <", item.label()),
+                    Style::default().add_modifier(Modifier::REVERSED),
+                )
+            } else {
+                Line::from(item.label())
+            }
+        })
+        .collect()
+}
+
+fn seed_lines(menu: &MainMenu) -> Vec<Line<'static>> {
+    vec![
+        Line::from("The same seed always builds the same dungeon."),
+        Line::from(""),
+        Line::from(format!("Seed: {}_", menu.seed_input)),
+    ]
+}
+
+fn confirm_new_run_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("You have a saved run."),
+        Line::from(""),
+        Line::styled(
+            "Starting a new one abandons it for good.",
+            Style::default().fg(Color::LightRed),
+        ),
+    ]
+}
+
+/// ` 1.    1234  depth 5   812 turns  Killed by an Oni`
+fn high_score_lines(menu: &MainMenu) -> Vec<Line<'static>> {
+    if menu.high_scores.entries.is_empty() {
+        return vec![Line::styled(
+            "No runs yet.",
+            Style::default().fg(Color::DarkGray),
+        )];
+    }
+    menu.high_scores
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            Line::from(format!(
+                "{:>2}. {:>6}  depth {:<2} {:>5} turns  {}",
+                i + 1,
+                entry.score,
+                entry.depth,
+                entry.turns,
+                entry.cause
+            ))
+        })
+        .collect()
+}
+
+fn option_lines(options: &Options) -> Vec<Line<'static>> {
+    let checkbox = |on: bool| if on { "[x]" } else { "[ ]" };
+    vec![Line::styled(
+        format!(
+            "{} Pick up items automatically",
+            checkbox(options.auto_pickup)
+        ),
+        Style::default().add_modifier(Modifier::REVERSED),
+    )]
+}
@@ -1,33 +1,141 @@
-use crate::{game::Game, world::tile::TileType};
+use crate::{
+    game::{Game, state::GameState},
+    item::Item,
+    message::Message,
+    spell::Spell,
+    ui::{camera::Camera, game_over::render_game_over, hud, main_menu::render_main_menu},
+    world::tile::TileType,
+};
 use ratatui::{
     Frame,
-    style::{Color, Style},
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
 };
 
+/// Inventory popup size (including borders)
+const INVENTORY_WIDTH: u16 = 44;
+const INVENTORY_HEIGHT: u16 = 20;
+
+/// Spell menu popup size (including borders)
+const SPELL_MENU_WIDTH: u16 = 40;
+const SPELL_MENU_HEIGHT: u16 = 8;
+
+/// Height of the message panel beneath the map (including borders)
+const MESSAGE_PANEL_HEIGHT: u16 = 6;
+
+/// Terminals at least this wide get the status panel beside the map
+const SIDE_PANEL_MIN_WIDTH: u16 = 80;
+const STATUS_PANEL_WIDTH: u16 = 28;
+/// Height of the one-line status bar used on narrower terminals (including borders)
+const STATUS_BAR_HEIGHT: u16 = 3;
+
+/// Lays out the screen depending on the terminal width
+///
+/// ```text
+/// wide:                              narrow:
+/// ┌Hyakki──────────────┐┌Status─┐   ┌Hyakki──────────┐
+/// │        map         ││ depth │   │      map       │
+/// │                    ││ HP/MP │   └Seed────────────┘
+/// └Seed────────────────┘└───────┘   ┌────────────────┐
+/// ┌Messages─────────────────────┐   │ D 3/10 | HP .. │
+/// │ latest messages             │   └────────────────┘
+/// └─────────────────────────────┘   ┌Messages────────┐
+///                                   └────────────────┘
+/// ```
 pub fn render(frame: &mut Frame, game: &Game) {
+    if game.state == GameState::MainMenu {
+        render_main_menu(frame, game);
+        return;
+    }
+
+    let [main_area, message_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(MESSAGE_PANEL_HEIGHT)])
+            .areas(frame.area());
+
+    if main_area.width >= SIDE_PANEL_MIN_WIDTH {
+        let [map_area, status_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(STATUS_PANEL_WIDTH)])
+                .areas(main_area);
+        render_map(frame, map_area, game);
+        hud::render_status_panel(frame, status_area, game);
+    } else {
+        let [map_area, status_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(STATUS_BAR_HEIGHT)])
+                .areas(main_area);
+        render_map(frame, map_area, game);
+        hud::render_status_bar(frame, status_area, game);
+    }
+    render_messages(frame, message_area, game);
+
+    match game.state {
+        GameState::ShowInventory => render_inventory(frame, game),
+        GameState::SelectSpell => render_spell_menu(frame, game),
+        GameState::MessageHistory => render_message_history(frame, game),
+        GameState::GameOver => render_game_over(frame, game),
+        _ => {}
+    }
+}
+
+/// Draws the part of the map around the player that fits in `area`
+fn render_map(frame: &mut Frame, area: Rect, game: &Game) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Hyakki - 百鬼")
+        .title_bottom(format!("Seed {}", game.seed));
+
+    // Only the part of the map that fits inside the border is drawn
+    let view = block.inner(area);
+    let camera = Camera::new(
+        game.player_pos(),
+        view.width,
+        view.height,
+        game.dungeon.width,
+        game.dungeon.height,
+    );
+
     let mut lines: Vec<Line> = Vec::new();
     let mut char_buf = [0u8; 4];
 
-    for y in 0..game.dungeon.height {
+    // Tiles to highlight while aiming a spell
+    let (spell_area, target_cursor) = match game.targeting {
+        Some(t) => (game.spell_area(t.spell, t.cursor), Some(t.cursor)),
+        None => (Vec::new(), None),
+    };
+
+    for dy in 0..camera.height {
         let mut spans: Vec<Span> = Vec::new();
-        for x in 0..game.dungeon.width {
-            let px = game.player.position.x;
-            let py = game.player.position.y;
-            let is_player_here = px >= 0 && py >= 0 && px as usize == x && py as usize == y;
+        for dx in 0..camera.width {
+            let pos = camera.to_map(dx, dy);
 
-            let (ch, style) = if is_player_here {
-                (game.player.to_char(), Style::default().fg(Color::Yellow))
+            let tile = game.dungeon.tiles[pos.y as usize][pos.x as usize];
+            let visible = game.dungeon.is_visible(pos);
+            // Entities (the player, monsters and floor items) are drawn over
+            // everything the dungeon itself holds
+            let (ch, style) = if let Some(entity) = game.visible_entity_at(pos) {
+                (entity.glyph, Style::default().fg(entity.color))
+            } else if let Some(trap) = game.dungeon.known_trap_at(pos).filter(|_| visible) {
+                (trap.to_char(), Style::default().fg(trap.kind.color()))
+            } else if let Some(trap) = game
+                .dungeon
+                .known_trap_at(pos)
+                .filter(|_| game.dungeon.is_explored(pos))
+            {
+                (trap.to_char(), remembered_style())
+            } else if visible {
+                (tile.to_char(), tile_style(tile))
+            } else if game.dungeon.is_explored(pos) {
+                (tile.to_char(), remembered_style())
             } else {
-                let tile = game.dungeon.tiles[y][x];
-                let ch = tile.to_char();
-                let color = match tile {
-                    TileType::Wall => Color::Gray,
-                    TileType::Floor => Color::DarkGray,
-                    TileType::StairsDown => Color::Cyan,
-                };
-                (ch, Style::default().fg(color))
+                (' ', Style::default())
+            };
+            let style = if target_cursor == Some(pos) {
+                style.add_modifier(Modifier::REVERSED)
+            } else if spell_area.contains(&pos) {
+                style.bg(Color::Red)
+            } else {
+                style
             };
             // Use stack-allocated buffer to avoid heap allocation
             let s = ch.encode_utf8(&mut char_buf);
@@ -36,11 +144,202 @@ pub fn render(frame: &mut Frame, game: &Game) {
         lines.push(Line::from(spans));
     }
 
-    let paragraph = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("Hyakki - 百鬼"),
+    let paragraph = Paragraph::new(lines).block(block);
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Draws the most recent messages that fit in the panel, newest at the bottom
+fn render_messages(frame: &mut Frame, area: Rect, game: &Game) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Messages")
+        .title_bottom("m: history");
+    let rows = block.inner(area).height as usize;
+    let lines: Vec<Line> = game
+        .messages
+        .iter()
+        .rev()
+        .take(rows)
+        .rev()
+        .map(message_line)
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Draws the whole message log as a large popup, scrolled back by `message_scroll`
+fn render_message_history(frame: &mut Frame, game: &Game) {
+    let screen = frame.area();
+    let area = centered_rect(
+        screen,
+        screen.width.saturating_sub(4),
+        screen.height.saturating_sub(2),
     );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Message history ({})", game.messages.len()))
+        .title_bottom("↑/↓: scroll  m: close");
+    let rows = block.inner(area).height as usize;
+    let lines: Vec<Line> = game
+        .messages
+        .iter()
+        .rev()
+        .skip(game.message_scroll)
+        .take(rows)
+        .rev()
+        .map(message_line)
+        .collect();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// One message row: `[  42] You hit the Kappa for 3.`
+fn message_line(message: &Message) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("[{:>4}] ", message.turn),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            message.text.clone(),
+            Style::default().fg(message.kind.color()),
+        ),
+    ])
+}
+
+/// Draws the spell book as a centered popup; unaffordable spells are dimmed
+fn render_spell_menu(frame: &mut Frame, game: &Game) {
+    let area = centered_rect(frame.area(), SPELL_MENU_WIDTH, SPELL_MENU_HEIGHT);
+    let book = &game.player().spellbook;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Spells (MP {}/{})", book.mana, book.max_mana))
+        .title_bottom("z: cast  q: cancel");
+
+    let items: Vec<ListItem> = book
+        .spells
+        .iter()
+        .enumerate()
+        .map(|(i, &spell)| {
+            let style = if book.can_cast(spell) {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            ListItem::new(spell_line(i, spell)).style(style)
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(game.spell_cursor));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// One spell menu row: `a) Foxfire Bolt      3 MP`
+fn spell_line(index: usize, spell: Spell) -> Line<'static> {
+    let letter = (b'a' + index as u8) as char;
+    Line::from(format!(
+        "{}) {:<20} {:>2} MP",
+        letter,
+        spell.name(),
+        spell.mana_cost()
+    ))
+}
+
+/// Draws the inventory as a centered popup over the map
+fn render_inventory(frame: &mut Frame, game: &Game) {
+    let area = centered_rect(frame.area(), INVENTORY_WIDTH, INVENTORY_HEIGHT);
+    let inventory = game.inventory();
+    let title = format!("Inventory ({}/{})", inventory.len(), inventory.capacity);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_bottom("u: use  d: drop  i: close");
+
+    frame.render_widget(Clear, area);
+
+    if inventory.is_empty() {
+        let paragraph = Paragraph::new(equipment_lines(game))
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = inventory
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| ListItem::new(inventory_line(i, item)))
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(game.inventory_cursor));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// One inventory row: `a) ! Potion of Healing`
+fn inventory_line(index: usize, item: &Item) -> Line<'static> {
+    let letter = (b'a' + index as u8) as char;
+    Line::from(vec![
+        Span::raw(format!("{}) ", letter)),
+        Span::styled(
+            item.to_char().to_string(),
+            Style::default().fg(item.color()),
+        ),
+        Span::raw(format!(" {}", item.name())),
+    ])
+}
+
+/// Shown in place of the item list when nothing is carried
+fn equipment_lines(game: &Game) -> Vec<Line<'static>> {
+    let slot = |item: Option<Item>| item.map_or("(none)", |i| i.name());
+    vec![
+        Line::from("You are not carrying anything."),
+        Line::from(""),
+        Line::from(format!("Weapon: {}", slot(game.player().weapon))),
+        Line::from(format!("Armour: {}", slot(game.player().armour))),
+    ]
+}
+
+/// A `width x height` rectangle centered in `area`, shrunk to fit if needed
+pub(crate) fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+/// Style for a tile currently in view
+fn tile_style(tile: TileType) -> Style {
+    let color = match tile {
+        // Secret doors must not stand out from the wall around them
+        TileType::Wall | TileType::DoorSecret => Color::Gray,
+        TileType::Floor => Color::DarkGray,
+        TileType::StairsDown | TileType::StairsUp => Color::Cyan,
+        TileType::DoorClosed | TileType::DoorOpen => Color::Yellow,
+        TileType::DoorLocked => Color::LightRed,
+        TileType::ShallowWater => Color::LightBlue,
+        TileType::DeepWater => Color::Blue,
+        TileType::Lava => Color::Red,
+        TileType::Chasm => Color::Magenta,
+        TileType::Bridge => Color::Yellow,
+    };
+    Style::default().fg(color)
+}
 
-    frame.render_widget(paragraph, frame.area());
+/// Style for a tile that has been explored but is out of view
+fn remembered_style() -> Style {
+    Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::DIM)
 }
@@ -1,6 +1,10 @@
+pub mod arena;
 pub mod dungeon;
+pub mod fov;
 pub mod generator;
+pub mod line;
 pub mod tile;
+pub mod trap;
 
 pub use dungeon::Dungeon;
 pub use generator::Room;
@@ -0,0 +1,133 @@
+use crate::{
+    ecs::Entities,
+    entity::{
+        monster::{Monster, MonsterKind},
+        position::Position,
+    },
+    game::config::{DUNGEON_HEIGHT, DUNGEON_WIDTH},
+    world::{dungeon::Dungeon, generator::Room, tile::TileType},
+};
+
+/// The hall of Nurarihyon, leader of the night parade, on the deepest floor
+///
+/// Legend: `#` wall, `.` floor, `<` up-stairs (where the player arrives),
+/// `N` Nurarihyon, `O` Oni guard. Spaces are solid rock.
+const BOSS_ARENA: [&str; 15] = [
+    "###############################",
+    "#.............................#",
+    "#.............................#",
+    "#...#.....#....N....#.....#...#",
+    "#.............................#",
+    "#......O...............O......#",
+    "#...#.....#.........#.....#...#",
+    "#.............................#",
+    "#.............................#",
+    "#...#.....#.........#.....#...#",
+    "#.............................#",
+    "##############...##############",
+    "             #...#             ",
+    "             #.<.#             ",
+    "             #####             ",
+];
+
+impl Dungeon {
+    /// Builds the hand-designed boss floor from `BOSS_ARENA`, centred on the map
+    ///
+    /// There are no down-stairs: the way on is back up, carrying the artifact
+    /// the boss drops.
+    pub fn new_boss_arena(depth: u32) -> Self {
+        let arena_width = BOSS_ARENA[0].len();
+        let arena_height = BOSS_ARENA.len();
+        let left = (DUNGEON_WIDTH - arena_width) / 2;
+        let top = (DUNGEON_HEIGHT - arena_height) / 2;
+
+        let mut tiles = vec![vec![TileType::Wall; DUNGEON_WIDTH]; DUNGEON_HEIGHT];
+        let mut population = Entities::default();
+        let mut player_start = None;
+
+        for (dy, row) in BOSS_ARENA.iter().enumerate() {
+            for (dx, ch) in row.chars().enumerate() {
+                let (x, y) = (left + dx, top + dy);
+                let pos = Position {
+                    x: x as i32,
+                    y: y as i32,
+                };
+                tiles[y][x] = match ch {
+                    '.' => TileType::Floor,
+                    '<' => {
+                        player_start = Some(pos);
+                        TileType::StairsUp
+                    }
+                    'N' => {
+                        Monster::spawn(&mut population, MonsterKind::Nurarihyon, pos);
+                        TileType::Floor
+                    }
+                    'O' => {
+                        Monster::spawn(&mut population, MonsterKind::Oni, pos);
+                        TileType::Floor
+                    }
+                    _ => TileType::Wall,
+                };
+            }
+        }
+
+        let player_start = player_start.expect("The boss arena has up-stairs");
+        // The hall inside the outer wall, for anything that looks at rooms
+        let hall = Room::new(left as i32 + 1, top as i32 + 1, arena_width as i32 - 2, 10);
+
+        Dungeon {
+            tiles,
+            width: DUNGEON_WIDTH,
+            height: DUNGEON_HEIGHT,
+            rooms: vec![hall],
+            depth,
+            player_start,
+            // No way down; arriving from below never happens
+            stairs_position: player_start,
+            visible: vec![vec![false; DUNGEON_WIDTH]; DUNGEON_HEIGHT],
+            explored: vec![vec![false; DUNGEON_WIDTH]; DUNGEON_HEIGHT],
+            population,
+            traps: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_rows_have_equal_width() {
+        assert!(
+            BOSS_ARENA
+                .iter()
+                .all(|row| row.len() == BOSS_ARENA[0].len())
+        );
+    }
+
+    #[test]
+    fn test_arena_has_boss_and_no_way_down() {
+        let arena = Dungeon::new_boss_arena(10);
+        assert_eq!(arena.get_tile(arena.player_start), Some(TileType::StairsUp));
+        assert!(
+            arena
+                .tiles
+                .iter()
+                .flatten()
+                .all(|&tile| tile != TileType::StairsDown)
+        );
+        let population = &arena.population;
+        let bosses = population
+            .monster
+            .iter()
+            .filter(|(_, m)| m.kind.is_boss())
+            .count();
+        assert_eq!(bosses, 1);
+        assert!(population.monsters().into_iter().all(|monster| {
+            population
+                .position
+                .get(monster)
+                .is_some_and(|&pos| arena.is_walkable(pos))
+        }));
+    }
+}
@@ -1,11 +1,27 @@
 use crate::{
-    entity::position::Position,
+    ecs::Entities,
+    entity::{
+        monster::{Monster, MonsterKind},
+        position::Position,
+    },
     game::config::{
-        DUNGEON_HEIGHT, DUNGEON_WIDTH, MAX_ROOM_SIZE, MAX_ROOMS, MIN_ROOM_SIZE, MIN_ROOMS,
+        DUNGEON_HEIGHT, DUNGEON_WIDTH, FOV_RADIUS, MAX_DEPTH, MAX_ITEMS_PER_ROOM,
+        MAX_MONSTERS_PER_ROOM, MAX_TRAPS_PER_FLOOR,
+    },
+    item::{Item, ItemKind},
+    world::{
+        fov::compute_fov,
+        generator::{
+            MapGenerator, MapLayout, Room, Spawn, builtin_vaults, generator_for_depth, lay_hazards,
+            place_vaults, reachable_tiles,
+        },
+        line::line,
+        tile::TileType,
+        trap::{Trap, TrapKind},
     },
-    world::{generator::Room, tile::TileType},
 };
-use rand::Rng;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
 
 // Fixed test room constants (Phase 1)
 const ROOM_Y_START: usize = 20;
@@ -17,6 +33,25 @@ const ROOM_X_END: usize = 45;
 pub const ROOM_CENTER_X: i32 = (ROOM_X_START + ROOM_X_END) as i32 / 2;
 pub const ROOM_CENTER_Y: i32 = (ROOM_Y_START + ROOM_Y_END) as i32 / 2;
 
+/// Most traps a floor at `depth` can have: one more every other floor, up
+/// to `MAX_TRAPS_PER_FLOOR`
+fn max_traps(depth: u32) -> usize {
+    (1 + depth as usize / 2).min(MAX_TRAPS_PER_FLOOR)
+}
+
+/// Seed for the floor at `depth` in the run with `run_seed`
+///
+/// Every floor gets its own RNG, so a floor's layout does not depend on how
+/// much randomness was consumed on the floors above it.
+pub fn floor_seed(run_seed: u64, depth: u32) -> u64 {
+    // SplitMix64 finalizer, so neighbouring depths get unrelated seeds
+    let mut z = run_seed.wrapping_add((depth as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Dungeon {
     pub tiles: Vec<Vec<TileType>>,
     pub width: usize,
@@ -25,6 +60,15 @@ pub struct Dungeon {
     pub depth: u32,
     pub player_start: Position,
     pub stairs_position: Position,
+    /// Tiles currently in the player's field of view
+    pub visible: Vec<Vec<bool>>,
+    /// Tiles the player has seen at least once on this floor
+    pub explored: Vec<Vec<bool>>,
+    /// The monsters and items of this floor while the player is elsewhere;
+    /// on the current floor they live in `Game::entities` and this is empty
+    pub population: Entities,
+    /// Traps set into the floor, found or not
+    pub traps: Vec<Trap>,
 }
 
 impl Dungeon {
@@ -53,9 +97,25 @@ impl Dungeon {
                 x: ROOM_CENTER_X,
                 y: ROOM_CENTER_Y,
             },
+            visible: vec![vec![false; DUNGEON_WIDTH]; DUNGEON_HEIGHT],
+            explored: vec![vec![false; DUNGEON_WIDTH]; DUNGEON_HEIGHT],
+            population: Entities::default(),
+            traps: vec![],
         }
     }
 
+    /// Generates the floor at `depth` for the run with `run_seed`
+    ///
+    /// The same seed and depth always produce the same floor. The deepest
+    /// floor is always the boss arena.
+    pub fn new_seeded(depth: u32, run_seed: u64) -> Self {
+        if depth == MAX_DEPTH {
+            return Self::new_boss_arena(depth);
+        }
+        let mut rng = StdRng::seed_from_u64(floor_seed(run_seed, depth));
+        Self::new_random_with_rng(depth, &mut rng)
+    }
+
     /// Creates a randomly generated dungeon
     pub fn new_random(depth: u32) -> Self {
         let mut rng = rand::thread_rng();
@@ -64,58 +124,46 @@ impl Dungeon {
 
     /// Creates a randomly generated dungeon with a provided RNG (for testing)
     pub fn new_random_with_rng<R: Rng>(depth: u32, rng: &mut R) -> Self {
-        // Initialize all tiles as walls
-        let mut tiles = vec![vec![TileType::Wall; DUNGEON_WIDTH]; DUNGEON_HEIGHT];
-        let mut rooms = Vec::new();
-
-        // Determine desired number of rooms (may not be reached due to MAX_ATTEMPTS)
-        let desired_room_count = rng.gen_range(MIN_ROOMS..=MAX_ROOMS);
-        let mut attempts = 0;
-        const MAX_ATTEMPTS: usize = 200;
-
-        // Try to place rooms
-        while rooms.len() < desired_room_count && attempts < MAX_ATTEMPTS {
-            let width = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE);
-            let height = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE);
-            let x = rng.gen_range(1..(DUNGEON_WIDTH as i32 - width - 1));
-            let y = rng.gen_range(1..(DUNGEON_HEIGHT as i32 - height - 1));
-
-            let new_room = Room::new(x, y, width, height);
-
-            // Check if this room intersects with any existing room
-            let intersects = rooms.iter().any(|r: &Room| r.intersects(&new_room));
-
-            if !intersects {
-                // Carve out the room
-                Self::carve_room(&mut tiles, &new_room);
-
-                // Connect to previous room with corridor
-                if !rooms.is_empty() {
-                    let prev_center = rooms.last().unwrap().center();
-                    let new_center = new_room.center();
-                    Self::carve_corridor(&mut tiles, prev_center, new_center, rng);
-                }
-
-                rooms.push(new_room);
-            }
+        Self::with_generator(generator_for_depth(depth).as_ref(), depth, rng)
+    }
 
-            attempts += 1;
-        }
+    /// Lays out a floor with `generator`, floods parts of it with hazard
+    /// terrain and stamps in vaults, then adds stairs, monsters, items and
+    /// traps
+    pub fn with_generator<R: Rng>(generator: &dyn MapGenerator, depth: u32, rng: &mut R) -> Self {
+        let mut layout = generator.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, depth, rng);
+        lay_hazards(&mut layout, depth, rng);
+        let vault_spawns = place_vaults(&mut layout, &builtin_vaults(), depth, rng);
+        let MapLayout {
+            mut tiles,
+            rooms,
+            player_start,
+            stairs_position,
+        } = layout;
 
-        // Determine player start and stairs positions
-        // Panic if no rooms were generated - this indicates a bug in the generation algorithm
-        let player_start = rooms
-            .first()
-            .map(|r| r.center())
-            .expect("Dungeon generation failed: no rooms were placed");
-        let stairs_position = rooms
-            .last()
-            .map(|r| r.center())
-            .expect("Dungeon generation failed: no rooms were placed");
-
-        // Place stairs
+        // Place stairs; the up-stairs where the player arrives lead back up, or
+        // out of the dungeon on the first floor
+        tiles[player_start.y as usize][player_start.x as usize] = TileType::StairsUp;
         tiles[stairs_position.y as usize][stairs_position.x as usize] = TileType::StairsDown;
 
+        let mut population = Entities::default();
+        Self::spawn_monsters(&mut population, &tiles, &rooms, depth, rng);
+        Self::spawn_items(&mut population, &tiles, &rooms, depth, rng);
+        for (position, spawn) in vault_spawns {
+            match spawn {
+                Spawn::Monster(kind) => {
+                    let kind = kind.unwrap_or_else(|| MonsterKind::random_for_depth(depth, rng));
+                    Monster::spawn(&mut population, kind, position);
+                }
+                Spawn::Item(kind) => {
+                    let kind = kind.unwrap_or_else(|| ItemKind::random_for_depth(depth, rng));
+                    Item::spawn(&mut population, Item::new(kind), position);
+                }
+            }
+        }
+        Self::place_keys(&mut population, &tiles, player_start, rng);
+        let traps = Self::place_traps(&population, &tiles, &rooms, depth, rng);
+
         Dungeon {
             tiles,
             width: DUNGEON_WIDTH,
@@ -124,96 +172,137 @@ impl Dungeon {
             depth,
             player_start,
             stairs_position,
+            visible: vec![vec![false; DUNGEON_WIDTH]; DUNGEON_HEIGHT],
+            explored: vec![vec![false; DUNGEON_WIDTH]; DUNGEON_HEIGHT],
+            population,
+            traps,
         }
     }
 
-    /// Carves out a room (fills with Floor tiles)
-    ///
-    /// "Carve" means to sculpt/dig out space from solid walls, like carving stone.
-    /// This dungeon uses the "carving approach":
-    ///
-    /// ```text
-    /// 1. Start: Fill everything with walls
-    ///    ████████████
-    ///    ████████████
+    /// Places up to `MAX_MONSTERS_PER_ROOM` monsters in every room except the first
     ///
-    /// 2. Carve rooms: Remove walls to create floor
-    ///    ████████████
-    ///    ███┌──┐█████
-    ///    ███│  │█████  ← Walls become Floor
-    ///    ███└──┘█████
-    /// ```
-    ///
-    /// # Why `&mut [Vec<TileType>]` instead of `&mut [&mut [TileType]]`?
-    ///
-    /// - `Vec<Vec<T>>` auto-converts to `&mut [Vec<T>]` (Deref coercion)
-    /// - `Vec<Vec<T>>` does NOT auto-convert to `&mut [&mut [T]]`
-    /// - Using `&mut [&mut [T]]` would require manual conversion at call site
-    fn carve_room(tiles: &mut [Vec<TileType>], room: &Room) {
-        for y in room.y1..room.y2 {
-            for x in room.x1..room.x2 {
-                tiles[y as usize][x as usize] = TileType::Floor;
+    /// The first room is where the player starts, so it is left empty to
+    /// give the player a safe first turn. Spots flooded with hazard terrain
+    /// are skipped.
+    fn spawn_monsters<R: Rng>(
+        population: &mut Entities,
+        tiles: &[Vec<TileType>],
+        rooms: &[Room],
+        depth: u32,
+        rng: &mut R,
+    ) {
+        for room in rooms.iter().skip(1) {
+            let count = rng.gen_range(0..=MAX_MONSTERS_PER_ROOM);
+            for _ in 0..count {
+                let position = Position {
+                    x: rng.gen_range(room.x1..room.x2),
+                    y: rng.gen_range(room.y1..room.y2),
+                };
+                let occupied =
+                    position == room.center() || population.monster_at(position).is_some();
+                if !occupied && tiles[position.y as usize][position.x as usize].is_walkable() {
+                    let kind = MonsterKind::random_for_depth(depth, rng);
+                    Monster::spawn(population, kind, position);
+                }
             }
         }
     }
 
-    /// Carves an L-shaped corridor between two points
-    ///
-    /// # Arguments
-    /// * `tiles` - The dungeon tile grid to modify
-    /// * `start` - Starting position (typically center of previous room)
-    /// * `end` - Ending position (typically center of new room)
-    /// * `rng` - Random number generator for choosing corridor direction
+    /// Scatters up to `MAX_ITEMS_PER_ROOM` items in every room
     ///
-    /// # Why generic `R: Rng` instead of concrete type like `ThreadRng`?
-    ///
-    /// - Production: uses `rand::thread_rng()` (ThreadRng)
-    /// - Testing: uses `StdRng::seed_from_u64(seed)` for reproducible results
-    /// - Generic allows both, with static dispatch (no runtime cost)
-    fn carve_corridor<R: Rng>(
-        tiles: &mut [Vec<TileType>],
-        start: Position,
-        end: Position,
+    /// Room centers are skipped because they hold the player start and
+    /// stairs, and so are spots flooded with hazard terrain.
+    fn spawn_items<R: Rng>(
+        population: &mut Entities,
+        tiles: &[Vec<TileType>],
+        rooms: &[Room],
+        depth: u32,
         rng: &mut R,
     ) {
-        // Randomly choose horizontal-first or vertical-first
-        if rng.gen_bool(0.5) {
-            // Horizontal then vertical
-            Self::carve_horizontal_tunnel(tiles, start.x, end.x, start.y);
-            Self::carve_vertical_tunnel(tiles, start.y, end.y, end.x);
-        } else {
-            // Vertical then horizontal
-            Self::carve_vertical_tunnel(tiles, start.y, end.y, start.x);
-            Self::carve_horizontal_tunnel(tiles, start.x, end.x, end.y);
+        for room in rooms {
+            let count = rng.gen_range(0..=MAX_ITEMS_PER_ROOM);
+            for _ in 0..count {
+                let position = Position {
+                    x: rng.gen_range(room.x1..room.x2),
+                    y: rng.gen_range(room.y1..room.y2),
+                };
+                if position != room.center()
+                    && tiles[position.y as usize][position.x as usize].is_walkable()
+                {
+                    let item = Item::new(ItemKind::random_for_depth(depth, rng));
+                    Item::spawn(population, item, position);
+                }
+            }
         }
     }
 
-    /// Carves a horizontal tunnel at fixed Y coordinate
-    ///
-    /// # Arguments
-    /// * `tiles` - The dungeon tile grid to modify
-    /// * `x1`, `x2` - X coordinates to connect (order doesn't matter)
-    /// * `y` - Fixed Y coordinate for the tunnel
-    fn carve_horizontal_tunnel(tiles: &mut [Vec<TileType>], x1: i32, x2: i32, y: i32) {
-        let (min_x, max_x) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
-        for x in min_x..=max_x {
-            tiles[y as usize][x as usize] = TileType::Floor;
+    /// Drops a key for every locked door somewhere the player can reach from
+    /// `player_start` without unlocking anything or finding a secret door
+    fn place_keys<R: Rng>(
+        population: &mut Entities,
+        tiles: &[Vec<TileType>],
+        player_start: Position,
+        rng: &mut R,
+    ) {
+        let locked = tiles
+            .iter()
+            .flatten()
+            .filter(|&&tile| tile == TileType::DoorLocked)
+            .count();
+        if locked == 0 {
+            return;
         }
-    }
 
-    /// Carves a vertical tunnel at fixed X coordinate
-    ///
-    /// # Arguments
-    /// * `tiles` - The dungeon tile grid to modify
-    /// * `y1`, `y2` - Y coordinates to connect (order doesn't matter)
-    /// * `x` - Fixed X coordinate for the tunnel
-    fn carve_vertical_tunnel(tiles: &mut [Vec<TileType>], y1: i32, y2: i32, x: i32) {
-        let (min_y, max_y) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
-        for y in min_y..=max_y {
-            tiles[y as usize][x as usize] = TileType::Floor;
+        let open = reachable_tiles(tiles, player_start, |tile| {
+            tile.is_passable() && !tile.is_secret() && tile != TileType::DoorLocked
+        });
+        let spots: Vec<Position> = open
+            .into_iter()
+            .filter(|pos| tiles[pos.y as usize][pos.x as usize] == TileType::Floor)
+            .collect();
+        for _ in 0..locked {
+            if let Some(&position) = spots.choose(rng) {
+                Item::spawn(population, Item::new(ItemKind::Key), position);
+            }
         }
     }
 
+    /// Hides up to `max_traps(depth)` traps on plain floor, outside the first
+    /// room and off any item
+    fn place_traps<R: Rng>(
+        population: &Entities,
+        tiles: &[Vec<TileType>],
+        rooms: &[Room],
+        depth: u32,
+        rng: &mut R,
+    ) -> Vec<Trap> {
+        let spots: Vec<Position> = tiles
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter().enumerate().map(move |(x, &tile)| {
+                    let pos = Position {
+                        x: x as i32,
+                        y: y as i32,
+                    };
+                    (pos, tile)
+                })
+            })
+            .filter(|&(pos, tile)| {
+                tile == TileType::Floor
+                    && !rooms.first().is_some_and(|room| room.contains(pos))
+                    && population.item_at(pos).is_none()
+            })
+            .map(|(pos, _)| pos)
+            .collect();
+
+        let count = rng.gen_range(0..=max_traps(depth));
+        spots
+            .choose_multiple(rng, count)
+            .map(|&pos| Trap::new(TrapKind::random_for_depth(depth, rng), pos))
+            .collect()
+    }
+
     pub fn get_tile(&self, pos: Position) -> Option<TileType> {
         let x: usize = pos.x.try_into().ok()?;
         let y: usize = pos.y.try_into().ok()?;
@@ -223,11 +312,68 @@ impl Dungeon {
     pub fn is_walkable(&self, pos: Position) -> bool {
         self.get_tile(pos).is_some_and(|t| t.is_walkable())
     }
+
+    pub fn trap_index_at(&self, pos: Position) -> Option<usize> {
+        self.traps.iter().position(|t| t.position == pos)
+    }
+
+    /// The trap at `pos`, if the player knows about it
+    pub fn known_trap_at(&self, pos: Position) -> Option<&Trap> {
+        self.traps.iter().find(|t| t.position == pos && !t.hidden)
+    }
+
+    /// Whether nothing blocks sight on the straight line between two tiles
+    ///
+    /// The end points themselves are allowed to be opaque.
+    pub fn has_line_of_sight(&self, from: Position, to: Position) -> bool {
+        let path = line(from, to);
+        let between = &path[..path.len().saturating_sub(1)];
+        between
+            .iter()
+            .all(|&pos| self.get_tile(pos).is_some_and(|t| !t.blocks_sight()))
+    }
+
+    /// Recomputes the field of view from `origin` and marks newly seen tiles as explored
+    pub fn update_fov(&mut self, origin: Position) {
+        self.visible = compute_fov(self, origin, FOV_RADIUS);
+        for (explored_row, visible_row) in self.explored.iter_mut().zip(&self.visible) {
+            for (explored, &visible) in explored_row.iter_mut().zip(visible_row) {
+                *explored |= visible;
+            }
+        }
+    }
+
+    /// Marks every tile on the floor as explored (magic mapping)
+    pub fn reveal_map(&mut self) {
+        for row in &mut self.explored {
+            row.fill(true);
+        }
+    }
+
+    pub fn is_visible(&self, pos: Position) -> bool {
+        Self::grid_get(&self.visible, pos)
+    }
+
+    pub fn is_explored(&self, pos: Position) -> bool {
+        Self::grid_get(&self.explored, pos)
+    }
+
+    /// Bounds-checked lookup into a per-tile flag grid
+    fn grid_get(grid: &[Vec<bool>], pos: Position) -> bool {
+        let (Ok(x), Ok(y)) = (usize::try_from(pos.x), usize::try_from(pos.y)) else {
+            return false;
+        };
+        grid.get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(false)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::world::generator::{Connectivity, RoomsAndCorridors, all_generators};
 
     // ===== Fixed dungeon tests (Phase 1) =====
 
@@ -274,106 +420,116 @@ mod tests {
     }
 
     // ===== Random dungeon tests (Phase 2) =====
+    //
+    // Invariants every `MapGenerator` must keep are checked against a floor
+    // from each of them.
 
-    #[test]
-    fn test_dungeon_has_rooms() {
-        let dungeon = Dungeon::new_random(1);
-        assert!(!dungeon.rooms.is_empty());
-        assert!(dungeon.rooms.len() >= MIN_ROOMS);
-        assert!(dungeon.rooms.len() <= MAX_ROOMS);
+    /// A freshly generated floor from every generator, with the generator's name
+    fn floors_from_every_generator(depth: u32) -> Vec<(&'static str, Dungeon)> {
+        all_generators()
+            .iter()
+            .map(|generator| {
+                let mut rng = rand::thread_rng();
+                let dungeon = Dungeon::with_generator(generator.as_ref(), depth, &mut rng);
+                (generator.name(), dungeon)
+            })
+            .collect()
     }
 
-    #[test]
-    fn test_rooms_do_not_overlap() {
-        let dungeon = Dungeon::new_random(1);
-        for (i, room1) in dungeon.rooms.iter().enumerate() {
-            for room2 in dungeon.rooms.iter().skip(i + 1) {
-                // Check actual overlap (not just adjacency)
-                let overlaps = room1.x1 < room2.x2
-                    && room1.x2 > room2.x1
-                    && room1.y1 < room2.y2
-                    && room1.y2 > room2.y1;
-                assert!(!overlaps, "Rooms {:?} and {:?} overlap", room1, room2);
-            }
-        }
+    /// Where every monster waiting on `dungeon` stands
+    fn monster_positions(dungeon: &Dungeon) -> Vec<Position> {
+        let population = &dungeon.population;
+        population
+            .monsters()
+            .into_iter()
+            .filter_map(|monster| population.position.get(monster).copied())
+            .collect()
     }
 
-    #[test]
-    fn test_dungeon_has_stairs() {
-        let dungeon = Dungeon::new_random(1);
-        let stairs_count = dungeon
-            .tiles
+    /// Where every item of `kind` (or of any kind) lies on `dungeon`
+    fn item_positions(dungeon: &Dungeon, kind: Option<ItemKind>) -> Vec<Position> {
+        let population = &dungeon.population;
+        population
+            .item
             .iter()
-            .flat_map(|row| row.iter())
-            .filter(|&&tile| tile == TileType::StairsDown)
-            .count();
-        assert_eq!(stairs_count, 1);
+            .filter(|(_, item)| kind.is_none_or(|kind| item.kind == kind))
+            .filter_map(|(entity, _)| population.position.get(entity).copied())
+            .collect()
     }
 
-    #[test]
-    fn test_stairs_in_last_room() {
-        let dungeon = Dungeon::new_random(1);
-        let last_room = dungeon.rooms.last().unwrap();
-        let stairs = dungeon.stairs_position;
-        assert!(
-            stairs.x >= last_room.x1
-                && stairs.x < last_room.x2
-                && stairs.y >= last_room.y1
-                && stairs.y < last_room.y2,
-            "Stairs should be in last room"
-        );
+    fn has_boss(dungeon: &Dungeon) -> bool {
+        dungeon
+            .population
+            .monster
+            .iter()
+            .any(|(_, monster)| monster.kind.is_boss())
     }
 
     #[test]
-    fn test_player_start_in_first_room() {
-        let dungeon = Dungeon::new_random(1);
-        let first_room = &dungeon.rooms[0];
-        let start = dungeon.player_start;
-        assert!(
-            start.x >= first_room.x1
-                && start.x < first_room.x2
-                && start.y >= first_room.y1
-                && start.y < first_room.y2,
-            "Player should start in first room"
-        );
+    fn test_dungeon_has_stairs() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            let stairs_count = dungeon
+                .tiles
+                .iter()
+                .flat_map(|row| row.iter())
+                .filter(|&&tile| tile == TileType::StairsDown)
+                .count();
+            assert_eq!(stairs_count, 1, "{}", name);
+        }
     }
 
     #[test]
     fn test_all_rooms_connected() {
-        use std::collections::VecDeque;
-
-        let dungeon = Dungeon::new_random(1);
-        let start = dungeon.player_start;
-
-        let mut visited = vec![vec![false; dungeon.width]; dungeon.height];
-        let mut queue = VecDeque::new();
-        queue.push_back(start);
-        visited[start.y as usize][start.x as usize] = true;
-
-        while let Some(pos) = queue.pop_front() {
-            for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-                let next = pos.translate(dx, dy);
-                if next.x >= 0
-                    && next.x < dungeon.width as i32
-                    && next.y >= 0
-                    && next.y < dungeon.height as i32
-                    && !visited[next.y as usize][next.x as usize]
-                    && dungeon.is_walkable(next)
-                {
-                    visited[next.y as usize][next.x as usize] = true;
-                    queue.push_back(next);
+        for (name, dungeon) in floors_from_every_generator(1) {
+            let connectivity = Connectivity::new(&dungeon.tiles, dungeon.player_start);
+
+            // Check all room centers are reachable
+            for room in &dungeon.rooms {
+                let center = room.center();
+                assert!(
+                    connectivity.reachable(center),
+                    "{}: room center {:?} is not reachable",
+                    name,
+                    center
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_floor_reachable() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            let connectivity = Connectivity::new(&dungeon.tiles, dungeon.player_start);
+            for (y, row) in dungeon.tiles.iter().enumerate() {
+                for (x, tile) in row.iter().enumerate() {
+                    let pos = Position {
+                        x: x as i32,
+                        y: y as i32,
+                    };
+                    assert!(
+                        !tile.is_passable() || connectivity.reachable(pos),
+                        "{}: ({}, {}) is not reachable",
+                        name,
+                        x,
+                        y
+                    );
                 }
             }
         }
+    }
 
-        // Check all room centers are reachable
-        for room in &dungeon.rooms {
-            let center = room.center();
-            assert!(
-                visited[center.y as usize][center.x as usize],
-                "Room center {:?} is not reachable",
-                center
-            );
+    #[test]
+    fn test_map_edges_are_walls() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            let (w, h) = (dungeon.width, dungeon.height);
+            for x in 0..w {
+                assert_eq!(dungeon.tiles[0][x], TileType::Wall, "{}", name);
+                assert_eq!(dungeon.tiles[h - 1][x], TileType::Wall, "{}", name);
+            }
+            for row in &dungeon.tiles {
+                assert_eq!(row[0], TileType::Wall, "{}", name);
+                assert_eq!(row[w - 1], TileType::Wall, "{}", name);
+            }
         }
     }
 
@@ -385,36 +541,299 @@ mod tests {
 
     #[test]
     fn test_rooms_within_bounds() {
-        let dungeon = Dungeon::new_random(1);
-        for room in &dungeon.rooms {
-            assert!(room.x1 >= 1, "Room x1 should be >= 1");
-            assert!(room.y1 >= 1, "Room y1 should be >= 1");
+        for (name, dungeon) in floors_from_every_generator(1) {
+            for room in &dungeon.rooms {
+                assert!(room.x1 >= 1, "{}: room x1 should be >= 1", name);
+                assert!(room.y1 >= 1, "{}: room y1 should be >= 1", name);
+                assert!(
+                    (room.x2 as usize) < DUNGEON_WIDTH - 1,
+                    "{}: room x2 should be < width - 1",
+                    name
+                );
+                assert!(
+                    (room.y2 as usize) < DUNGEON_HEIGHT - 1,
+                    "{}: room y2 should be < height - 1",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_player_start_is_walkable() {
+        for (name, dungeon) in floors_from_every_generator(1) {
             assert!(
-                (room.x2 as usize) < DUNGEON_WIDTH - 1,
-                "Room x2 should be < width - 1"
+                dungeon.is_walkable(dungeon.player_start),
+                "{}: player start position should be walkable",
+                name
             );
+        }
+    }
+
+    #[test]
+    fn test_stairs_is_walkable() {
+        for (name, dungeon) in floors_from_every_generator(1) {
             assert!(
-                (room.y2 as usize) < DUNGEON_HEIGHT - 1,
-                "Room y2 should be < height - 1"
+                dungeon.is_walkable(dungeon.stairs_position),
+                "{}: stairs position should be walkable",
+                name
             );
         }
     }
 
     #[test]
-    fn test_player_start_is_walkable() {
-        let dungeon = Dungeon::new_random(1);
-        assert!(
-            dungeon.is_walkable(dungeon.player_start),
-            "Player start position should be walkable"
-        );
+    fn test_up_stairs_at_player_start() {
+        for depth in [1, 2] {
+            for (name, dungeon) in floors_from_every_generator(depth) {
+                assert_eq!(
+                    dungeon.get_tile(dungeon.player_start),
+                    Some(TileType::StairsUp),
+                    "{}",
+                    name
+                );
+            }
+        }
     }
 
     #[test]
-    fn test_stairs_is_walkable() {
-        let dungeon = Dungeon::new_random(1);
-        assert!(
-            dungeon.is_walkable(dungeon.stairs_position),
-            "Stairs position should be walkable"
-        );
+    fn test_deepest_floor_is_boss_arena() {
+        let dungeon = Dungeon::new_seeded(MAX_DEPTH, 12345);
+        assert!(has_boss(&dungeon));
+        assert!(!has_boss(&Dungeon::new_seeded(MAX_DEPTH - 1, 12345)));
+    }
+
+    #[test]
+    fn test_rooms_get_doors() {
+        let floors = (0..10).map(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            Dungeon::with_generator(&RoomsAndCorridors, 1, &mut rng)
+        });
+        let doors: usize = floors
+            .map(|d| d.tiles.iter().flatten().filter(|t| t.is_door()).count())
+            .sum();
+        assert!(doors > 0);
+    }
+
+    #[test]
+    fn test_every_locked_door_has_a_reachable_key() {
+        let mut locked_floors = 0;
+        for seed in 0..40 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let dungeon = Dungeon::with_generator(&RoomsAndCorridors, 3, &mut rng);
+            let locked = dungeon
+                .tiles
+                .iter()
+                .flatten()
+                .filter(|&&t| t == TileType::DoorLocked)
+                .count();
+            let keys = item_positions(&dungeon, Some(ItemKind::Key));
+            assert_eq!(keys.len(), locked, "Seed {}", seed);
+
+            let open = reachable_tiles(&dungeon.tiles, dungeon.player_start, |tile| {
+                tile.is_passable() && !tile.is_secret() && tile != TileType::DoorLocked
+            });
+            for key in keys {
+                assert!(open.contains(&key), "Seed {}: key behind a lock", seed);
+            }
+            locked_floors += usize::from(locked > 0);
+        }
+        assert!(locked_floors > 0, "No floor had a locked door");
+    }
+
+    #[test]
+    fn test_stairs_reachable_without_secrets() {
+        let mut secret_floors = 0;
+        for generator in all_generators() {
+            for depth in 1..MAX_DEPTH {
+                for seed in 0..50 {
+                    let mut rng = StdRng::seed_from_u64(seed * 31 + u64::from(depth));
+                    let dungeon = Dungeon::with_generator(generator.as_ref(), depth, &mut rng);
+                    let connectivity = Connectivity::new(&dungeon.tiles, dungeon.player_start);
+                    assert!(
+                        connectivity.reachable_without_secrets(dungeon.stairs_position),
+                        "{} depth {} seed {}: stairs behind a secret door",
+                        generator.name(),
+                        depth,
+                        seed
+                    );
+                    secret_floors +=
+                        usize::from(dungeon.tiles.iter().flatten().any(|t| t.is_secret()));
+                }
+            }
+        }
+        assert!(secret_floors > 0, "No floor had a secret door");
+    }
+
+    #[test]
+    fn test_same_seed_same_floor() {
+        let a = Dungeon::new_seeded(3, 12345);
+        let b = Dungeon::new_seeded(3, 12345);
+        assert_eq!(a.tiles, b.tiles);
+        assert_eq!(a.population, b.population);
+    }
+
+    #[test]
+    fn test_floors_of_a_run_differ() {
+        assert_ne!(floor_seed(12345, 1), floor_seed(12345, 2));
+        assert_ne!(floor_seed(1, 1), floor_seed(2, 1));
+        let first = Dungeon::new_seeded(1, 12345);
+        let second = Dungeon::new_seeded(2, 12345);
+        assert_ne!(first.tiles, second.tiles);
+    }
+
+    // ===== Field of view tests (Phase 3) =====
+
+    #[test]
+    fn test_nothing_explored_before_fov() {
+        let dungeon = Dungeon::new_fixed();
+        assert!(!dungeon.is_explored(dungeon.player_start));
+        assert!(!dungeon.is_visible(dungeon.player_start));
+    }
+
+    #[test]
+    fn test_update_fov_marks_visible_and_explored() {
+        let mut dungeon = Dungeon::new_fixed();
+        dungeon.update_fov(dungeon.player_start);
+        assert!(dungeon.is_visible(dungeon.player_start));
+        assert!(dungeon.is_explored(dungeon.player_start));
+    }
+
+    #[test]
+    fn test_explored_tiles_are_remembered() {
+        let mut dungeon = Dungeon::new_fixed();
+        let corner = Position { x: 35, y: 20 };
+        dungeon.update_fov(corner);
+        let far_corner = Position { x: 44, y: 29 };
+        assert!(!dungeon.is_visible(far_corner));
+
+        // Seen from the center, then the player walks back to the corner
+        dungeon.update_fov(dungeon.player_start);
+        dungeon.update_fov(corner);
+        assert!(!dungeon.is_visible(far_corner));
+        assert!(dungeon.is_explored(far_corner));
+    }
+
+    #[test]
+    fn test_visibility_out_of_bounds_is_false() {
+        let mut dungeon = Dungeon::new_fixed();
+        dungeon.update_fov(dungeon.player_start);
+        assert!(!dungeon.is_visible(Position { x: -1, y: 0 }));
+        assert!(!dungeon.is_explored(Position { x: 0, y: 50 }));
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let mut dungeon = Dungeon::new_fixed();
+        let a = Position { x: 36, y: 25 };
+        let b = Position { x: 43, y: 25 };
+        assert!(dungeon.has_line_of_sight(a, b));
+        dungeon.tiles[25][40] = TileType::Wall;
+        assert!(!dungeon.has_line_of_sight(a, b));
+        // Looking at the wall itself is fine
+        assert!(dungeon.has_line_of_sight(a, Position { x: 40, y: 25 }));
+    }
+
+    #[test]
+    fn test_reveal_map_explores_everything() {
+        let mut dungeon = Dungeon::new_fixed();
+        dungeon.reveal_map();
+        assert!(dungeon.is_explored(Position { x: 0, y: 0 }));
+        assert!(dungeon.is_explored(Position { x: 79, y: 49 }));
+        assert!(!dungeon.is_visible(Position { x: 0, y: 0 }));
+    }
+
+    // ===== Monster spawn tests (Phase 4) =====
+
+    #[test]
+    fn test_monsters_spawn_on_walkable_tiles() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            for pos in monster_positions(&dungeon) {
+                assert!(
+                    dungeon.is_walkable(pos),
+                    "{}: monster spawned on a wall at {:?}",
+                    name,
+                    pos
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_monsters_in_first_room() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            let first_room = &dungeon.rooms[0];
+            for p in monster_positions(&dungeon) {
+                assert!(
+                    !(p.x >= first_room.x1
+                        && p.x < first_room.x2
+                        && p.y >= first_room.y1
+                        && p.y < first_room.y2),
+                    "{}: monster spawned in the player's starting room",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_monsters_do_not_share_tiles() {
+        for (_, dungeon) in floors_from_every_generator(1) {
+            let positions = monster_positions(&dungeon);
+            for (i, a) in positions.iter().enumerate() {
+                for b in positions.iter().skip(i + 1) {
+                    assert_ne!(a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_monster_count_bounded() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            let max = (dungeon.rooms.len() - 1) * MAX_MONSTERS_PER_ROOM;
+            assert!(dungeon.population.monsters().len() <= max, "{}", name);
+        }
+    }
+
+    // ===== Item tests (Phase 7) =====
+
+    #[test]
+    fn test_items_spawn_on_walkable_tiles() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            for pos in item_positions(&dungeon, None) {
+                assert!(dungeon.is_walkable(pos), "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_items_avoid_stairs_and_start() {
+        for (name, dungeon) in floors_from_every_generator(1) {
+            for pos in item_positions(&dungeon, None) {
+                assert_ne!(pos, dungeon.player_start, "{}", name);
+                assert_ne!(pos, dungeon.stairs_position, "{}", name);
+            }
+        }
+    }
+
+    // ===== Trap tests =====
+
+    #[test]
+    fn test_traps_hidden_on_floor_outside_first_room() {
+        for depth in [1, 5] {
+            for (name, dungeon) in floors_from_every_generator(depth) {
+                assert!(dungeon.traps.len() <= max_traps(depth), "{}", name);
+                for trap in &dungeon.traps {
+                    assert!(trap.hidden, "{}", name);
+                    assert_eq!(dungeon.get_tile(trap.position), Some(TileType::Floor));
+                    assert!(!dungeon.rooms[0].contains(trap.position), "{}", name);
+                    assert!(
+                        dungeon.population.item_at(trap.position).is_none(),
+                        "{}",
+                        name
+                    );
+                }
+            }
+        }
     }
 }
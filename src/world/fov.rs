@@ -0,0 +1,306 @@
+use crate::{entity::position::Position, world::dungeon::Dungeon};
+
+/// Computes the set of tiles visible from `origin` using symmetric shadowcasting
+///
+/// Returns a `height x width` grid where `true` marks a visible tile.
+/// Tiles whose `TileType::blocks_sight` is true stop vision but are themselves
+/// visible, so the walls of a room are drawn along with its floor.
+///
+/// The algorithm scans the four cardinal quadrants row by row, tracking the
+/// range of slopes that is still unobstructed:
+///
+/// ```text
+///         row 3   . . . . . . .      Each row is one step further from @.
+///         row 2     . . # . .        A wall narrows the slope range of the
+///         row 1       . . .          rows behind it, casting a "shadow".
+///                       @
+/// ```
+///
+/// # Why symmetric?
+///
+/// A floor tile is only revealed when its center lies inside the visible
+/// slope range. This makes vision symmetric: if A can see B, then B can see A.
+/// Monsters rely on this to decide whether they have noticed the player.
+pub fn compute_fov(dungeon: &Dungeon, origin: Position, radius: i32) -> Vec<Vec<bool>> {
+    let mut visible = vec![vec![false; dungeon.width]; dungeon.height];
+    mark_visible(&mut visible, origin);
+
+    for cardinal in Cardinal::ALL {
+        let quadrant = Quadrant { cardinal, origin };
+        let first_row = Row {
+            depth: 1,
+            start_slope: Slope::new(-1, 1),
+            end_slope: Slope::new(1, 1),
+        };
+        scan(dungeon, &quadrant, first_row, radius, &mut visible);
+    }
+
+    visible
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Cardinal {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Cardinal {
+    const ALL: [Cardinal; 4] = [
+        Cardinal::North,
+        Cardinal::East,
+        Cardinal::South,
+        Cardinal::West,
+    ];
+}
+
+/// One of the four 90-degree cones centered on the origin
+struct Quadrant {
+    cardinal: Cardinal,
+    origin: Position,
+}
+
+impl Quadrant {
+    /// Converts a (row depth, column) pair relative to this quadrant into a map position
+    fn transform(&self, depth: i32, col: i32) -> Position {
+        let Position { x, y } = self.origin;
+        match self.cardinal {
+            Cardinal::North => Position {
+                x: x + col,
+                y: y - depth,
+            },
+            Cardinal::South => Position {
+                x: x + col,
+                y: y + depth,
+            },
+            Cardinal::East => Position {
+                x: x + depth,
+                y: y + col,
+            },
+            Cardinal::West => Position {
+                x: x - depth,
+                y: y + col,
+            },
+        }
+    }
+}
+
+/// Exact rational slope (`num / den`, `den > 0`)
+///
+/// Floating point slopes are avoided because tile centers fall exactly on
+/// half-integer boundaries, where rounding errors would make results unstable.
+#[derive(Debug, Clone, Copy)]
+struct Slope {
+    num: i32,
+    den: i32,
+}
+
+impl Slope {
+    fn new(num: i32, den: i32) -> Self {
+        Slope { num, den }
+    }
+
+    /// Slope of the left edge of the tile at `(depth, col)`
+    fn of_tile(depth: i32, col: i32) -> Self {
+        Slope::new(2 * col - 1, 2 * depth)
+    }
+}
+
+/// A row of tiles at a fixed distance from the origin, bounded by two slopes
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    depth: i32,
+    start_slope: Slope,
+    end_slope: Slope,
+}
+
+impl Row {
+    /// Leftmost column, rounding `depth * start_slope` half up
+    fn min_col(&self) -> i32 {
+        let Slope { num, den } = self.start_slope;
+        (2 * self.depth * num + den).div_euclid(2 * den)
+    }
+
+    /// Rightmost column, rounding `depth * end_slope` half down
+    fn max_col(&self) -> i32 {
+        let Slope { num, den } = self.end_slope;
+        -((-2 * self.depth * num + den).div_euclid(2 * den))
+    }
+
+    fn next(&self) -> Row {
+        Row {
+            depth: self.depth + 1,
+            ..*self
+        }
+    }
+
+    /// Whether the center of the tile at `col` lies within this row's slopes
+    fn is_symmetric(&self, col: i32) -> bool {
+        col * self.start_slope.den >= self.depth * self.start_slope.num
+            && col * self.end_slope.den <= self.depth * self.end_slope.num
+    }
+}
+
+fn scan(
+    dungeon: &Dungeon,
+    quadrant: &Quadrant,
+    mut row: Row,
+    radius: i32,
+    visible: &mut [Vec<bool>],
+) {
+    if row.depth > radius {
+        return;
+    }
+
+    let mut prev_blocks: Option<bool> = None;
+    for col in row.min_col()..=row.max_col() {
+        let pos = quadrant.transform(row.depth, col);
+        let blocks = blocks_sight(dungeon, pos);
+        let in_radius = row.depth * row.depth + col * col <= radius * radius;
+
+        if in_radius && (blocks || row.is_symmetric(col)) {
+            mark_visible(visible, pos);
+        }
+        if prev_blocks == Some(true) && !blocks {
+            row.start_slope = Slope::of_tile(row.depth, col);
+        }
+        if prev_blocks == Some(false) && blocks {
+            let mut next_row = row.next();
+            next_row.end_slope = Slope::of_tile(row.depth, col);
+            scan(dungeon, quadrant, next_row, radius, visible);
+        }
+        prev_blocks = Some(blocks);
+    }
+
+    if prev_blocks == Some(false) {
+        scan(dungeon, quadrant, row.next(), radius, visible);
+    }
+}
+
+/// Out-of-bounds positions are treated as opaque so scanning stops at the map edge
+fn blocks_sight(dungeon: &Dungeon, pos: Position) -> bool {
+    dungeon.get_tile(pos).is_none_or(|t| t.blocks_sight())
+}
+
+fn mark_visible(visible: &mut [Vec<bool>], pos: Position) {
+    if let (Ok(x), Ok(y)) = (usize::try_from(pos.x), usize::try_from(pos.y))
+        && let Some(cell) = visible.get_mut(y).and_then(|row| row.get_mut(x))
+    {
+        *cell = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::tile::TileType;
+
+    const RADIUS: i32 = 8;
+
+    fn is_visible(visible: &[Vec<bool>], pos: Position) -> bool {
+        visible[pos.y as usize][pos.x as usize]
+    }
+
+    #[test]
+    fn test_origin_is_visible() {
+        let dungeon = Dungeon::new_fixed();
+        let origin = dungeon.player_start;
+        let visible = compute_fov(&dungeon, origin, RADIUS);
+        assert!(is_visible(&visible, origin));
+    }
+
+    #[test]
+    fn test_open_room_is_visible() {
+        let dungeon = Dungeon::new_fixed();
+        let origin = dungeon.player_start;
+        let visible = compute_fov(&dungeon, origin, RADIUS);
+        // Every floor tile of the 10x10 room is within radius of the center
+        for y in 20..30 {
+            for x in 35..45 {
+                assert!(
+                    is_visible(&visible, Position { x, y }),
+                    "({}, {}) should be visible",
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_room_walls_are_visible() {
+        let dungeon = Dungeon::new_fixed();
+        let visible = compute_fov(&dungeon, dungeon.player_start, RADIUS);
+        // The wall just above the room's top row
+        assert!(is_visible(&visible, Position { x: 40, y: 19 }));
+    }
+
+    #[test]
+    fn test_cannot_see_through_walls() {
+        let dungeon = Dungeon::new_fixed();
+        let visible = compute_fov(&dungeon, dungeon.player_start, RADIUS);
+        // Two tiles beyond the room's top wall
+        assert!(!is_visible(&visible, Position { x: 40, y: 18 }));
+    }
+
+    #[test]
+    fn test_radius_limits_vision() {
+        let dungeon = Dungeon::new_fixed();
+        let origin = dungeon.player_start;
+        let visible = compute_fov(&dungeon, origin, 2);
+        assert!(is_visible(&visible, origin.translate(2, 0)));
+        assert!(!is_visible(&visible, origin.translate(3, 0)));
+    }
+
+    #[test]
+    fn test_pillar_casts_shadow() {
+        let mut dungeon = Dungeon::new_fixed();
+        let origin = dungeon.player_start;
+        let pillar = origin.translate(2, 0);
+        dungeon.tiles[pillar.y as usize][pillar.x as usize] = TileType::Wall;
+
+        let visible = compute_fov(&dungeon, origin, RADIUS);
+        assert!(is_visible(&visible, pillar));
+        assert!(!is_visible(&visible, origin.translate(3, 0)));
+    }
+
+    #[test]
+    fn test_fov_is_symmetric() {
+        let mut dungeon = Dungeon::new_fixed();
+        // Scatter a few pillars to create partial shadows
+        for (x, y) in [(38, 22), (41, 24), (43, 27), (37, 26)] {
+            dungeon.tiles[y][x] = TileType::Wall;
+        }
+
+        let floors: Vec<Position> = (20..30)
+            .flat_map(|y| (35..45).map(move |x| Position { x, y }))
+            .filter(|&p| dungeon.get_tile(p) == Some(TileType::Floor))
+            .collect();
+
+        let views: Vec<Vec<Vec<bool>>> = floors
+            .iter()
+            .map(|&p| compute_fov(&dungeon, p, RADIUS * 2))
+            .collect();
+
+        for (i, &a) in floors.iter().enumerate() {
+            for (j, &b) in floors.iter().enumerate() {
+                assert_eq!(
+                    is_visible(&views[i], b),
+                    is_visible(&views[j], a),
+                    "Visibility between {:?} and {:?} is not symmetric",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_origin_near_edge_does_not_panic() {
+        let mut dungeon = Dungeon::new_fixed();
+        dungeon.tiles[0][0] = TileType::Floor;
+        let visible = compute_fov(&dungeon, Position { x: 0, y: 0 }, RADIUS);
+        assert!(is_visible(&visible, Position { x: 0, y: 0 }));
+    }
+}
@@ -1,7 +1,81 @@
-use crate::entity::position::Position;
+pub mod bsp;
+pub mod caves;
+pub mod drunkard;
+pub mod hazards;
+pub mod rooms;
+pub mod vault;
+
+use crate::{
+    entity::position::Position,
+    game::config::{DOOR_CHANCE, LOCKED_ROOM_CHANCE, SECRET_DOOR_CHANCE},
+    world::tile::TileType,
+};
+use rand::{Rng, RngCore, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+pub use bsp::Bsp;
+pub use caves::Caves;
+pub use drunkard::{DrunkardsWalk, WalkerSpawn};
+pub use hazards::lay_hazards;
+pub use rooms::RoomsAndCorridors;
+pub use vault::{Spawn, VaultTemplate, builtin_vaults, place_vaults};
+
+/// The shape of a floor as decided by a [`MapGenerator`]
+///
+/// Stairs tiles, monsters and items are added afterwards by `Dungeon`, so
+/// every generator gets them the same way.
+pub struct MapLayout {
+    pub tiles: Vec<Vec<TileType>>,
+    /// Areas monsters and items are spawned in; the first must contain
+    /// `player_start`, as it is kept free of monsters
+    pub rooms: Vec<Room>,
+    pub player_start: Position,
+    pub stairs_position: Position,
+}
+
+/// An algorithm that lays out the walls and floors of a level
+///
+/// Implementations must leave the outer edge of the map solid and make every
+/// floor tile reachable from `player_start`.
+pub trait MapGenerator {
+    /// Short description, used in test failure messages
+    fn name(&self) -> &'static str;
+
+    /// Lays out a `width` x `height` floor for `depth`
+    ///
+    /// Takes `&mut dyn RngCore` rather than a generic RNG so that generators
+    /// can be chosen at runtime as trait objects.
+    fn generate(&self, width: usize, height: usize, depth: u32, rng: &mut dyn RngCore)
+    -> MapLayout;
+}
+
+/// The generator used for floors at `depth`
+///
+/// Floors mostly alternate between the two room-based styles, with caves
+/// and winding mines breaking things up now and then.
+pub fn generator_for_depth(depth: u32) -> Box<dyn MapGenerator> {
+    match depth {
+        3 | 7 => Box::new(Caves),
+        5 | 9 => Box::new(DrunkardsWalk::default()),
+        _ if depth.is_multiple_of(2) => Box::new(Bsp),
+        _ => Box::new(RoomsAndCorridors),
+    }
+}
+
+/// Every generator, so that shared invariants can be checked against all of them
+pub fn all_generators() -> Vec<Box<dyn MapGenerator>> {
+    vec![
+        Box::new(RoomsAndCorridors),
+        Box::new(Bsp),
+        Box::new(Caves),
+        Box::new(DrunkardsWalk::with_spawn(WalkerSpawn::Centre)),
+        Box::new(DrunkardsWalk::with_spawn(WalkerSpawn::RandomFloor)),
+        Box::new(DrunkardsWalk::with_spawn(WalkerSpawn::Anywhere)),
+    ]
+}
 
 /// Represents a rectangular room in the dungeon
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Room {
     pub x1: i32,
     pub y1: i32,
@@ -28,6 +102,11 @@ impl Room {
         }
     }
 
+    /// Whether `pos` lies inside the room
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x >= self.x1 && pos.x < self.x2 && pos.y >= self.y1 && pos.y < self.y2
+    }
+
     /// Checks if this room intersects with another room (with 1 tile margin)
     ///
     /// Returns true when rooms overlap or are within 1 tile of each other:
@@ -55,6 +134,505 @@ impl Room {
     }
 }
 
+/// Carves out a room (fills with Floor tiles)
+///
+/// "Carve" means to sculpt/dig out space from solid walls, like carving stone.
+/// This dungeon uses the "carving approach":
+///
+/// ```text
+/// 1. Start: Fill everything with walls
+///    ████████████
+///    ████████████
+///
+/// 2. Carve rooms: Remove walls to create floor
+///    ████████████
+///    ███┌──┐█████
+///    ███│  │█████  ← Walls become Floor
+///    ███└──┘█████
+/// ```
+///
+/// # Why `&mut [Vec<TileType>]` instead of `&mut [&mut [TileType]]`?
+///
+/// - `Vec<Vec<T>>` auto-converts to `&mut [Vec<T>]` (Deref coercion)
+/// - `Vec<Vec<T>>` does NOT auto-convert to `&mut [&mut [T]]`
+/// - Using `&mut [&mut [T]]` would require manual conversion at call site
+pub(crate) fn carve_room(tiles: &mut [Vec<TileType>], room: &Room) {
+    for y in room.y1..room.y2 {
+        for x in room.x1..room.x2 {
+            tiles[y as usize][x as usize] = TileType::Floor;
+        }
+    }
+}
+
+/// Carves an L-shaped corridor between two points, returning the tiles it
+/// runs through in order from `start` to `end`
+///
+/// # Arguments
+/// * `tiles` - The dungeon tile grid to modify
+/// * `start` - Starting position (typically center of previous room)
+/// * `end` - Ending position (typically center of new room)
+/// * `rng` - Random number generator for choosing corridor direction
+///
+/// # Why `R: Rng + ?Sized` instead of `&mut dyn RngCore`?
+///
+/// - Generators: pass on the `&mut dyn RngCore` that `MapGenerator::generate`
+///   receives, which `?Sized` lets through unboxed
+/// - Testing: passes a concrete `StdRng::seed_from_u64(seed)` directly
+/// - Either way the floor depends only on the RNG, so seeded runs repeat exactly
+pub(crate) fn carve_corridor<R: Rng + ?Sized>(
+    tiles: &mut [Vec<TileType>],
+    start: Position,
+    end: Position,
+    rng: &mut R,
+) -> Vec<Position> {
+    // Randomly choose horizontal-first or vertical-first; the corner tile is
+    // carved by both legs, so it is dropped from the second
+    let (mut path, rest) = if rng.gen_bool(0.5) {
+        // Horizontal then vertical
+        (
+            carve_horizontal_tunnel(tiles, start.x, end.x, start.y),
+            carve_vertical_tunnel(tiles, start.y, end.y, end.x),
+        )
+    } else {
+        // Vertical then horizontal
+        (
+            carve_vertical_tunnel(tiles, start.y, end.y, start.x),
+            carve_horizontal_tunnel(tiles, start.x, end.x, end.y),
+        )
+    };
+    path.extend(rest.into_iter().skip(1));
+    path
+}
+
+/// Carves a horizontal tunnel at fixed Y coordinate, returning its tiles in
+/// order from `x1` to `x2`
+///
+/// # Arguments
+/// * `tiles` - The dungeon tile grid to modify
+/// * `x1`, `x2` - X coordinates to connect (either may be the larger)
+/// * `y` - Fixed Y coordinate for the tunnel
+pub(crate) fn carve_horizontal_tunnel(
+    tiles: &mut [Vec<TileType>],
+    x1: i32,
+    x2: i32,
+    y: i32,
+) -> Vec<Position> {
+    let step = if x1 <= x2 { 1 } else { -1 };
+    let tunnel: Vec<Position> = (0..=(x2 - x1).abs())
+        .map(|i| Position {
+            x: x1 + i * step,
+            y,
+        })
+        .collect();
+    for pos in &tunnel {
+        tiles[pos.y as usize][pos.x as usize] = TileType::Floor;
+    }
+    tunnel
+}
+
+/// Carves a vertical tunnel at fixed X coordinate, returning its tiles in
+/// order from `y1` to `y2`
+///
+/// # Arguments
+/// * `tiles` - The dungeon tile grid to modify
+/// * `y1`, `y2` - Y coordinates to connect (either may be the larger)
+/// * `x` - Fixed X coordinate for the tunnel
+pub(crate) fn carve_vertical_tunnel(
+    tiles: &mut [Vec<TileType>],
+    y1: i32,
+    y2: i32,
+    x: i32,
+) -> Vec<Position> {
+    let step = if y1 <= y2 { 1 } else { -1 };
+    let tunnel: Vec<Position> = (0..=(y2 - y1).abs())
+        .map(|i| Position {
+            x,
+            y: y1 + i * step,
+        })
+        .collect();
+    for pos in &tunnel {
+        tiles[pos.y as usize][pos.x as usize] = TileType::Floor;
+    }
+    tunnel
+}
+
+/// Puts doors in the doorways where corridors enter `rooms`, and now and
+/// then locks the only door of a dead-end room
+///
+/// A doorway is a floor tile just outside a room with wall on both sides,
+/// so a corridor running along a room's wall doesn't get a door. A room with
+/// a single opening can be locked without cutting off anything beyond it;
+/// rooms holding any of `keep_open` (the start and stairs) never are.
+///
+/// ```text
+/// ####.####      ####+####
+/// #.......#      #.......#
+/// #.......#  →   #.......#
+/// #..........    #.......+..
+/// #########      #########
+/// ```
+pub(crate) fn place_doors(
+    tiles: &mut [Vec<TileType>],
+    rooms: &[Room],
+    keep_open: &[Position],
+    rng: &mut dyn RngCore,
+) {
+    let mut dead_ends = Vec::new();
+
+    for room in rooms {
+        let openings = room_openings(tiles, room);
+        let doorways: Vec<Position> = openings
+            .iter()
+            .filter(|&&(pos, along_x)| is_doorway(tiles, pos, along_x))
+            .map(|&(pos, _)| pos)
+            .collect();
+
+        for &doorway in &doorways {
+            if rng.gen_bool(DOOR_CHANCE) {
+                tiles[doorway.y as usize][doorway.x as usize] = TileType::DoorClosed;
+            }
+        }
+        if openings.len() == 1
+            && doorways.len() == 1
+            && !keep_open.iter().any(|&pos| room.contains(pos))
+        {
+            dead_ends.push(doorways[0]);
+        }
+    }
+
+    if rng.gen_bool(LOCKED_ROOM_CHANCE)
+        && let Some(door) = dead_ends.choose(rng)
+    {
+        tiles[door.y as usize][door.x as usize] = TileType::DoorLocked;
+    }
+}
+
+/// Ends some of the `corridors` carved between `rooms` in a secret door,
+/// which passes for wall until the player searches next to it
+///
+/// The door goes where the corridor leaves one of the rooms it joins, and
+/// only if that spot is a doorway. It is only hidden if the stairs can still
+/// be reached from `player_start` without finding a single secret door, so
+/// secrets only ever guard side rooms and shortcuts. Locked doors are left
+/// alone.
+///
+/// ```text
+/// #########      #########
+/// #.......#      #.......#
+/// #...........   #.......#...
+/// #########  →   #########
+/// ```
+pub(crate) fn hide_corridor_ends(
+    tiles: &mut [Vec<TileType>],
+    rooms: &[Room],
+    corridors: &[Vec<Position>],
+    player_start: Position,
+    stairs_position: Position,
+    rng: &mut dyn RngCore,
+) {
+    for corridor in corridors {
+        if !rng.gen_bool(SECRET_DOOR_CHANCE) {
+            continue;
+        }
+        let ends: Vec<Position> = [
+            corridor_end(tiles, rooms, corridor.iter()),
+            corridor_end(tiles, rooms, corridor.iter().rev()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let Some(&end) = ends.choose(rng) else {
+            continue;
+        };
+
+        let tile = tiles[end.y as usize][end.x as usize];
+        tiles[end.y as usize][end.x as usize] = TileType::DoorSecret;
+        if !Connectivity::new(tiles, player_start).reachable_without_secrets(stairs_position) {
+            tiles[end.y as usize][end.x as usize] = tile;
+        }
+    }
+}
+
+/// The first tile of `path` outside every room, if the path steps onto it
+/// from a room through a doorway that isn't locked
+fn corridor_end<'a>(
+    tiles: &[Vec<TileType>],
+    rooms: &[Room],
+    path: impl Iterator<Item = &'a Position>,
+) -> Option<Position> {
+    let mut previous = None;
+    for &pos in path {
+        if rooms.iter().any(|room| room.contains(pos)) {
+            previous = Some(pos);
+            continue;
+        }
+        // A step along x crosses a wall running along y, and the other way round
+        let along_x = previous?.x == pos.x;
+        let tile = tile_at(tiles, pos)?;
+        return (tile.is_passable()
+            && tile != TileType::DoorLocked
+            && is_doorway(tiles, pos, along_x))
+        .then_some(pos);
+    }
+    None
+}
+
+/// Passable tiles in the ring around `room` (corners excluded), each with
+/// whether the ring runs along the x axis there
+fn room_openings(tiles: &[Vec<TileType>], room: &Room) -> Vec<(Position, bool)> {
+    let top = (room.x1..room.x2).map(|x| (Position { x, y: room.y1 - 1 }, true));
+    let bottom = (room.x1..room.x2).map(|x| (Position { x, y: room.y2 }, true));
+    let left = (room.y1..room.y2).map(|y| (Position { x: room.x1 - 1, y }, false));
+    let right = (room.y1..room.y2).map(|y| (Position { x: room.x2, y }, false));
+    top.chain(bottom)
+        .chain(left)
+        .chain(right)
+        .filter(|&(pos, _)| tile_at(tiles, pos).is_some_and(|t| t.is_passable()))
+        .collect()
+}
+
+/// Whether the opening at `pos` has wall on both sides along the ring
+fn is_doorway(tiles: &[Vec<TileType>], pos: Position, along_x: bool) -> bool {
+    let (a, b) = if along_x {
+        (pos.translate(-1, 0), pos.translate(1, 0))
+    } else {
+        (pos.translate(0, -1), pos.translate(0, 1))
+    };
+    tile_at(tiles, a) == Some(TileType::Wall) && tile_at(tiles, b) == Some(TileType::Wall)
+}
+
+/// Steps between neighbouring tiles; the player and monsters move orthogonally
+const ORTHOGONAL: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// Side length of the square open areas synthesised as rooms on room-less maps
+const OPEN_AREA_SIZE: i32 = 3;
+
+fn tile_at(tiles: &[Vec<TileType>], pos: Position) -> Option<TileType> {
+    let x = usize::try_from(pos.x).ok()?;
+    let y = usize::try_from(pos.y).ok()?;
+    tiles.get(y).and_then(|row| row.get(x)).copied()
+}
+
+/// Floods out from `start`, stepping only onto tiles that `can_enter`
+/// accepts, and returns every tile reached with its walking distance,
+/// nearest first
+///
+/// The one breadth-first search behind every reachability question the
+/// generators and their tests ask.
+pub(crate) fn flood_fill(
+    tiles: &[Vec<TileType>],
+    start: Position,
+    can_enter: impl Fn(TileType) -> bool,
+) -> Vec<(Position, u32)> {
+    let mut seen = vec![vec![false; tiles[0].len()]; tiles.len()];
+    seen[start.y as usize][start.x as usize] = true;
+    let mut reached = vec![(start, 0)];
+    let mut next = 0;
+    while let Some(&(pos, distance)) = reached.get(next) {
+        next += 1;
+        for (dx, dy) in ORTHOGONAL {
+            let neighbour = pos.translate(dx, dy);
+            if tile_at(tiles, neighbour).is_some_and(&can_enter)
+                && !seen[neighbour.y as usize][neighbour.x as usize]
+            {
+                seen[neighbour.y as usize][neighbour.x as usize] = true;
+                reached.push((neighbour, distance + 1));
+            }
+        }
+    }
+    reached
+}
+
+/// Walking distance from `start` to every tile, `None` where it can't be
+/// reached even through doors
+pub(crate) fn walk_distances(tiles: &[Vec<TileType>], start: Position) -> Vec<Vec<Option<u32>>> {
+    let mut distances = vec![vec![None; tiles[0].len()]; tiles.len()];
+    for (pos, distance) in flood_fill(tiles, start, |tile| tile.is_passable()) {
+        distances[pos.y as usize][pos.x as usize] = Some(distance);
+    }
+    distances
+}
+
+/// Every tile reachable from `start` stepping only onto tiles that `can_enter`
+/// accepts
+pub(crate) fn reachable_tiles(
+    tiles: &[Vec<TileType>],
+    start: Position,
+    can_enter: impl Fn(TileType) -> bool,
+) -> Vec<Position> {
+    flood_fill(tiles, start, can_enter)
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// Which tiles of a floor can be reached from a starting point, telling
+/// apart what can be reached at all from what can be reached without finding
+/// a secret door
+///
+/// Locked doors count as a way through either way, since their keys are
+/// always left somewhere reachable.
+pub struct Connectivity {
+    reachable: Vec<Vec<bool>>,
+    without_secrets: Vec<Vec<bool>>,
+}
+
+impl Connectivity {
+    pub fn new(tiles: &[Vec<TileType>], start: Position) -> Self {
+        let grid = |can_enter: &dyn Fn(TileType) -> bool| {
+            let mut grid = vec![vec![false; tiles[0].len()]; tiles.len()];
+            for pos in reachable_tiles(tiles, start, can_enter) {
+                grid[pos.y as usize][pos.x as usize] = true;
+            }
+            grid
+        };
+        Connectivity {
+            reachable: grid(&|tile| tile.is_passable()),
+            without_secrets: grid(&|tile| tile.is_passable() && !tile.is_secret()),
+        }
+    }
+
+    /// Whether `pos` can be reached, going through secret doors if need be
+    pub fn reachable(&self, pos: Position) -> bool {
+        Self::get(&self.reachable, pos)
+    }
+
+    /// Whether `pos` can be reached without finding any secret door
+    pub fn reachable_without_secrets(&self, pos: Position) -> bool {
+        Self::get(&self.without_secrets, pos)
+    }
+
+    fn get(grid: &[Vec<bool>], pos: Position) -> bool {
+        usize::try_from(pos.y)
+            .ok()
+            .zip(usize::try_from(pos.x).ok())
+            .and_then(|(y, x)| grid.get(y).and_then(|row| row.get(x)))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+/// Groups the passable tiles into connected regions, largest first
+pub(crate) fn floor_regions(tiles: &[Vec<TileType>]) -> Vec<Vec<Position>> {
+    regions_where(tiles, |tile| tile.is_passable())
+}
+
+/// Groups the passable tiles into the regions a player can walk between
+/// without finding a secret door, largest first
+pub(crate) fn open_regions(tiles: &[Vec<TileType>]) -> Vec<Vec<Position>> {
+    regions_where(tiles, |tile| tile.is_passable() && !tile.is_secret())
+}
+
+fn regions_where(
+    tiles: &[Vec<TileType>],
+    can_enter: impl Fn(TileType) -> bool,
+) -> Vec<Vec<Position>> {
+    let mut seen = vec![vec![false; tiles[0].len()]; tiles.len()];
+    let mut regions = Vec::new();
+
+    for (y, row) in tiles.iter().enumerate() {
+        for (x, &tile) in row.iter().enumerate() {
+            if seen[y][x] || !can_enter(tile) {
+                continue;
+            }
+            let start = Position {
+                x: x as i32,
+                y: y as i32,
+            };
+            let region = reachable_tiles(tiles, start, &can_enter);
+            for pos in &region {
+                seen[pos.y as usize][pos.x as usize] = true;
+            }
+            regions.push(region);
+        }
+    }
+
+    regions.sort_by_key(|region| std::cmp::Reverse(region.len()));
+    regions
+}
+
+/// Turns every passable tile outside `region` back into wall
+pub(crate) fn keep_only(tiles: &mut [Vec<TileType>], region: &[Position]) {
+    let mut keep = vec![vec![false; tiles[0].len()]; tiles.len()];
+    for pos in region {
+        keep[pos.y as usize][pos.x as usize] = true;
+    }
+    for (row, keep_row) in tiles.iter_mut().zip(&keep) {
+        for (tile, &keep) in row.iter_mut().zip(keep_row) {
+            if tile.is_passable() && !keep {
+                *tile = TileType::Wall;
+            }
+        }
+    }
+}
+
+/// Two tiles in the region around `from` that are about as far apart as the
+/// region allows
+///
+/// Walks to the tile farthest from `from`, then to the tile farthest from that.
+pub(crate) fn far_apart(tiles: &[Vec<TileType>], from: Position) -> (Position, Position) {
+    let farthest = |start: Position| {
+        let mut best = (0, start);
+        for (y, row) in walk_distances(tiles, start).iter().enumerate() {
+            for (x, distance) in row.iter().enumerate() {
+                if let Some(distance) = *distance
+                    && distance > best.0
+                {
+                    best = (
+                        distance,
+                        Position {
+                            x: x as i32,
+                            y: y as i32,
+                        },
+                    );
+                }
+            }
+        }
+        best.1
+    };
+    let first = farthest(from);
+    (first, farthest(first))
+}
+
+/// Rooms for maps without any: `player_start` as a one-tile first room (so
+/// nothing spawns on top of the player), then up to `count` small fully open
+/// squares scattered around the map, none of them touching `avoid`
+pub(crate) fn open_areas(
+    tiles: &[Vec<TileType>],
+    player_start: Position,
+    avoid: &[Position],
+    count: usize,
+    rng: &mut dyn RngCore,
+) -> Vec<Room> {
+    let is_open = |room: &Room| {
+        (room.y1..room.y2).all(|y| {
+            (room.x1..room.x2).all(|x| tile_at(tiles, Position { x, y }) == Some(TileType::Floor))
+        })
+    };
+
+    // Like generated rooms, open areas keep a tile of wall inside the outer wall
+    let mut candidates: Vec<Room> = (1..tiles.len() as i32 - OPEN_AREA_SIZE - 1)
+        .flat_map(|y| {
+            (1..tiles[0].len() as i32 - OPEN_AREA_SIZE - 1)
+                .map(move |x| Room::new(x, y, OPEN_AREA_SIZE, OPEN_AREA_SIZE))
+        })
+        .filter(|room| is_open(room))
+        .filter(|room| !room.contains(player_start) && !avoid.iter().any(|&p| room.contains(p)))
+        .collect();
+    candidates.shuffle(rng);
+
+    let mut rooms = vec![Room::new(player_start.x, player_start.y, 1, 1)];
+    for candidate in candidates {
+        if rooms.len() > count {
+            break;
+        }
+        if !rooms.iter().any(|room| room.intersects(&candidate)) {
+            rooms.push(candidate);
+        }
+    }
+    rooms
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +654,15 @@ mod tests {
         assert_eq!(center.y, 15);
     }
 
+    #[test]
+    fn test_room_contains() {
+        let room = Room::new(10, 10, 5, 5);
+        assert!(room.contains(Position { x: 10, y: 10 }));
+        assert!(room.contains(Position { x: 14, y: 14 }));
+        assert!(!room.contains(Position { x: 15, y: 12 }));
+        assert!(!room.contains(Position { x: 12, y: 9 }));
+    }
+
     #[test]
     fn test_rooms_intersect() {
         let room1 = Room::new(0, 0, 10, 10);
@@ -98,4 +685,48 @@ mod tests {
         let room2 = Room::new(6, 0, 5, 5); // Only 1 tile gap
         assert!(room1.intersects(&room2));
     }
+
+    #[test]
+    fn test_corridor_ends_at_room_doorways() {
+        let mut tiles = vec![vec![TileType::Wall; 20]; 9];
+        let first = Room::new(1, 1, 4, 3);
+        let second = Room::new(12, 4, 4, 3);
+        carve_room(&mut tiles, &first);
+        carve_room(&mut tiles, &second);
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+        let corridor = carve_corridor(&mut tiles, first.center(), second.center(), &mut rng);
+        let rooms = [first, second];
+
+        assert_eq!(corridor.first(), Some(&first.center()));
+        assert_eq!(corridor.last(), Some(&second.center()));
+        // Heads east out of the first room, then turns south into the second
+        assert_eq!(
+            corridor_end(&tiles, &rooms, corridor.iter()),
+            Some(Position { x: 5, y: 2 })
+        );
+        assert_eq!(
+            corridor_end(&tiles, &rooms, corridor.iter().rev()),
+            Some(Position { x: 14, y: 3 })
+        );
+    }
+
+    #[test]
+    fn test_connectivity_tells_secret_paths_apart() {
+        // Floor, secret door, floor, wall, floor
+        let tiles = vec![vec![
+            TileType::Floor,
+            TileType::DoorSecret,
+            TileType::Floor,
+            TileType::Wall,
+            TileType::Floor,
+        ]];
+        let at = |x| Position { x, y: 0 };
+        let connectivity = Connectivity::new(&tiles, at(0));
+
+        assert!(connectivity.reachable_without_secrets(at(0)));
+        assert!(connectivity.reachable(at(2)));
+        assert!(!connectivity.reachable_without_secrets(at(2)));
+        assert!(!connectivity.reachable(at(4)));
+        assert!(!connectivity.reachable(at(9)));
+    }
 }
@@ -0,0 +1,244 @@
+use crate::{
+    entity::position::Position,
+    game::config::{MAX_ROOM_SIZE, MAX_ROOMS, MIN_ROOM_SIZE, MIN_ROOMS},
+    world::{
+        generator::{
+            MapGenerator, MapLayout, Room, carve_corridor, carve_room, hide_corridor_ends,
+            place_doors,
+        },
+        tile::TileType,
+    },
+};
+use rand::{Rng, RngCore};
+
+/// Smallest area a leaf may have on either side: a minimum room plus a wall on each side
+const MIN_LEAF_SIZE: i32 = MIN_ROOM_SIZE + 2;
+
+/// How far (in tiles) a split may stray from the proportional position
+const SPLIT_JITTER: i32 = 4;
+
+/// Binary space partition: the map is split in two again and again until
+/// there is one leaf per room, then every leaf gets a room and sibling
+/// subtrees are joined by corridors
+///
+/// ```text
+/// ┌───────────┬─────────────────┐
+/// │  ┌───┐    │   ┌───────┐     │
+/// │  │ 1 │────┼───│   3   │     │
+/// │  └─┬─┘    │   └───┬───┘     │
+/// ├────┼──────┤       │         │
+/// │  ┌─┴──┐   │    ┌──┴──┐      │
+/// │  │ 2  │   │    │  4  │      │
+/// │  └────┘   │    └─────┘      │
+/// └───────────┴─────────────────┘
+/// ```
+///
+/// Unlike rejection sampling this always produces the number of rooms it
+/// aims for, spread evenly over the map. Doorways get doors as in
+/// [`RoomsAndCorridors`](super::RoomsAndCorridors).
+pub struct Bsp;
+
+/// A node of the partition tree
+struct Node {
+    area: Room,
+    children: Option<Box<[Node; 2]>>,
+}
+
+impl MapGenerator for Bsp {
+    fn name(&self) -> &'static str {
+        "binary space partition"
+    }
+
+    fn generate(
+        &self,
+        width: usize,
+        height: usize,
+        _depth: u32,
+        rng: &mut dyn RngCore,
+    ) -> MapLayout {
+        let mut tiles = vec![vec![TileType::Wall; width]; height];
+        let room_count = rng.gen_range(MIN_ROOMS..=MAX_ROOMS);
+
+        // The outermost row and column of the map stay solid
+        let area = Room::new(1, 1, width as i32 - 2, height as i32 - 2);
+        let tree = split(area, room_count, rng);
+
+        let mut rooms = Vec::new();
+        let mut corridors = Vec::new();
+        connect(&tree, &mut tiles, &mut rooms, &mut corridors, rng);
+
+        let player_start = rooms[0].center();
+        let stairs_position = rooms[rooms.len() - 1].center();
+        place_doors(&mut tiles, &rooms, &[player_start, stairs_position], rng);
+        hide_corridor_ends(
+            &mut tiles,
+            &rooms,
+            &corridors,
+            player_start,
+            stairs_position,
+            rng,
+        );
+
+        MapLayout {
+            tiles,
+            rooms,
+            player_start,
+            stairs_position,
+        }
+    }
+}
+
+/// Partitions `area` into exactly `leaves` leaves
+///
+/// The longer side is cut at a point proportional to how the leaves are
+/// shared out between the halves, moved a little at random so the grid
+/// doesn't look regular.
+fn split(area: Room, leaves: usize, rng: &mut dyn RngCore) -> Node {
+    if leaves <= 1 {
+        return Node {
+            area,
+            children: None,
+        };
+    }
+
+    let first_leaves = leaves / 2;
+    let second_leaves = leaves - first_leaves;
+    let width = area.x2 - area.x1;
+    let height = area.y2 - area.y1;
+    let vertical = width >= height;
+    let length = if vertical { width } else { height };
+
+    let ideal = length * first_leaves as i32 / leaves as i32;
+    let lowest = MIN_LEAF_SIZE;
+    let highest = (length - MIN_LEAF_SIZE).max(lowest);
+    let cut = (ideal + rng.gen_range(-SPLIT_JITTER..=SPLIT_JITTER)).clamp(lowest, highest);
+
+    let (first, second) = if vertical {
+        (
+            Room::new(area.x1, area.y1, cut, height),
+            Room::new(area.x1 + cut, area.y1, width - cut, height),
+        )
+    } else {
+        (
+            Room::new(area.x1, area.y1, width, cut),
+            Room::new(area.x1, area.y1 + cut, width, height - cut),
+        )
+    };
+
+    Node {
+        area,
+        children: Some(Box::new([
+            split(first, first_leaves, rng),
+            split(second, second_leaves, rng),
+        ])),
+    }
+}
+
+/// Carves a room in every leaf below `node` and joins sibling subtrees
+///
+/// Rooms are appended to `rooms` in tree order, so the first and last rooms
+/// are on opposite sides of the map, and the corridors joining them to
+/// `corridors`. Returns a room of this subtree for the
+/// parent to connect to.
+fn connect(
+    node: &Node,
+    tiles: &mut [Vec<TileType>],
+    rooms: &mut Vec<Room>,
+    corridors: &mut Vec<Vec<Position>>,
+    rng: &mut dyn RngCore,
+) -> Room {
+    let Some(children) = &node.children else {
+        let room = room_in(node.area, rng);
+        carve_room(tiles, &room);
+        rooms.push(room);
+        return room;
+    };
+
+    let first = connect(&children[0], tiles, rooms, corridors, rng);
+    let second = connect(&children[1], tiles, rooms, corridors, rng);
+    corridors.push(carve_corridor(tiles, first.center(), second.center(), rng));
+    if rng.gen_bool(0.5) { first } else { second }
+}
+
+/// A randomly sized and placed room inside `leaf`, leaving a wall on every side
+fn room_in(leaf: Room, rng: &mut dyn RngCore) -> Room {
+    let max_width = (leaf.x2 - leaf.x1 - 2).min(MAX_ROOM_SIZE);
+    let max_height = (leaf.y2 - leaf.y1 - 2).min(MAX_ROOM_SIZE);
+    let width = rng.gen_range(MIN_ROOM_SIZE.min(max_width)..=max_width);
+    let height = rng.gen_range(MIN_ROOM_SIZE.min(max_height)..=max_height);
+    let x = rng.gen_range(leaf.x1 + 1..=leaf.x2 - 1 - width);
+    let y = rng.gen_range(leaf.y1 + 1..=leaf.y2 - 1 - height);
+    Room::new(x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::config::{DUNGEON_HEIGHT, DUNGEON_WIDTH};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn layout(seed: u64) -> MapLayout {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Bsp.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng)
+    }
+
+    #[test]
+    fn test_room_count_is_guaranteed() {
+        for seed in 0..200 {
+            let rooms = layout(seed).rooms.len();
+            assert!(
+                (MIN_ROOMS..=MAX_ROOMS).contains(&rooms),
+                "Seed {} gave {} rooms",
+                seed,
+                rooms
+            );
+        }
+    }
+
+    #[test]
+    fn test_rooms_do_not_touch() {
+        for seed in 0..50 {
+            let rooms = layout(seed).rooms;
+            for (i, a) in rooms.iter().enumerate() {
+                for b in rooms.iter().skip(i + 1) {
+                    assert!(!a.intersects(b), "Seed {}: {:?} touches {:?}", seed, a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rooms_respect_size_limits() {
+        for seed in 0..50 {
+            for room in layout(seed).rooms {
+                let (w, h) = (room.x2 - room.x1, room.y2 - room.y1);
+                assert!((MIN_ROOM_SIZE..=MAX_ROOM_SIZE).contains(&w), "{:?}", room);
+                assert!((MIN_ROOM_SIZE..=MAX_ROOM_SIZE).contains(&h), "{:?}", room);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_makes_requested_leaves() {
+        fn count(node: &Node) -> usize {
+            match &node.children {
+                Some(children) => children.iter().map(count).sum(),
+                None => 1,
+            }
+        }
+        let mut rng = StdRng::seed_from_u64(7);
+        let area = Room::new(1, 1, 78, 48);
+        for leaves in 1..=MAX_ROOMS {
+            assert_eq!(count(&split(area, leaves, &mut rng)), leaves);
+        }
+    }
+
+    #[test]
+    fn test_start_and_stairs_far_apart() {
+        for seed in 0..20 {
+            let layout = layout(seed);
+            let distance = layout.player_start.distance_squared(layout.stairs_position);
+            assert!(distance > 10 * 10, "Seed {}: too close", seed);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    game::config::MAX_ROOMS,
+    world::{
+        generator::{MapGenerator, MapLayout, far_apart, floor_regions, keep_only, open_areas},
+        tile::TileType,
+    },
+};
+use rand::{Rng, RngCore};
+
+/// Chance that a tile starts out as wall before smoothing
+const INITIAL_WALL_CHANCE: f64 = 0.45;
+
+/// Number of smoothing passes
+const SMOOTHING_STEPS: usize = 5;
+
+/// A tile becomes wall when at least this many tiles of the 3x3 block around
+/// it (itself included) are walls
+const WALL_THRESHOLD: usize = 5;
+
+/// Smallest share of the map the kept cave must cover; smaller caves are rerolled
+const MIN_CAVE_FRACTION: f64 = 0.35;
+
+/// Rerolls before settling for whatever cave came out last
+const MAX_ATTEMPTS: usize = 10;
+
+/// Natural caverns grown with a cellular automaton
+///
+/// ```text
+/// random noise       after smoothing     largest cave kept
+/// #.##..#.#.#.       ############        ############
+/// ..#.#..##..#       ##....####.#        ##....######
+/// #..#.#...#.#   →   #......##..#   →    #......#####
+/// .#...##.#..#       #.........##        #.........##
+/// ##.#..#.##.#       ############        ############
+/// ```
+///
+/// Caves have no rooms, so a few small open areas are marked as rooms for
+/// monsters and items to spawn in.
+pub struct Caves;
+
+impl MapGenerator for Caves {
+    fn name(&self) -> &'static str {
+        "cellular automata caves"
+    }
+
+    fn generate(
+        &self,
+        width: usize,
+        height: usize,
+        _depth: u32,
+        rng: &mut dyn RngCore,
+    ) -> MapLayout {
+        let min_floor = (width * height) as f64 * MIN_CAVE_FRACTION;
+        let mut attempt = 0;
+        let (tiles, cave) = loop {
+            attempt += 1;
+            let mut tiles = random_fill(width, height, rng);
+            for _ in 0..SMOOTHING_STEPS {
+                tiles = smooth(&tiles);
+            }
+            let cave = floor_regions(&tiles).into_iter().next().unwrap_or_default();
+            if cave.len() as f64 >= min_floor || (attempt >= MAX_ATTEMPTS && !cave.is_empty()) {
+                break (tiles, cave);
+            }
+        };
+
+        let mut tiles = tiles;
+        keep_only(&mut tiles, &cave);
+
+        let from = cave[rng.gen_range(0..cave.len())];
+        let (player_start, stairs_position) = far_apart(&tiles, from);
+        let rooms = open_areas(&tiles, player_start, &[stairs_position], MAX_ROOMS, rng);
+
+        MapLayout {
+            tiles,
+            rooms,
+            player_start,
+            stairs_position,
+        }
+    }
+}
+
+/// Whether (`x`, `y`) is on the outer wall or the ring just inside it, which
+/// stay solid so caves keep the same margin as generated rooms
+fn on_border(x: i32, y: i32, width: i32, height: i32) -> bool {
+    x < 2 || y < 2 || x >= width - 2 || y >= height - 2
+}
+
+/// Random noise with a solid border
+fn random_fill(width: usize, height: usize, rng: &mut dyn RngCore) -> Vec<Vec<TileType>> {
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let border = on_border(x as i32, y as i32, width as i32, height as i32);
+                    if border || rng.gen_bool(INITIAL_WALL_CHANCE) {
+                        TileType::Wall
+                    } else {
+                        TileType::Floor
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// One automaton step: tiles crowded by walls fill in, the rest open up
+///
+/// The border stays solid, and off-map neighbours count as walls.
+fn smooth(tiles: &[Vec<TileType>]) -> Vec<Vec<TileType>> {
+    let height = tiles.len() as i32;
+    let width = tiles[0].len() as i32;
+    let is_wall = |x: i32, y: i32| {
+        x < 0
+            || y < 0
+            || x >= width
+            || y >= height
+            || tiles[y as usize][x as usize] == TileType::Wall
+    };
+
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let walls = (-1..=1)
+                        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                        .filter(|&(dx, dy)| is_wall(x + dx, y + dy))
+                        .count();
+                    if on_border(x, y, width, height) || walls >= WALL_THRESHOLD {
+                        TileType::Wall
+                    } else {
+                        TileType::Floor
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::config::{DUNGEON_HEIGHT, DUNGEON_WIDTH};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn layout(seed: u64) -> MapLayout {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Caves.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng)
+    }
+
+    #[test]
+    fn test_cave_is_one_region() {
+        for seed in 0..20 {
+            assert_eq!(floor_regions(&layout(seed).tiles).len(), 1, "Seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_cave_is_large() {
+        for seed in 0..20 {
+            let floor = layout(seed)
+                .tiles
+                .iter()
+                .flatten()
+                .filter(|t| t.is_walkable())
+                .count();
+            assert!(floor >= DUNGEON_WIDTH * DUNGEON_HEIGHT / 4, "Seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_start_and_stairs_far_apart() {
+        for seed in 0..20 {
+            let layout = layout(seed);
+            let distance = layout.player_start.distance_squared(layout.stairs_position);
+            assert!(distance > 20 * 20, "Seed {}: too close", seed);
+        }
+    }
+
+    #[test]
+    fn test_open_areas_are_floor() {
+        let layout = layout(3);
+        assert!(layout.rooms.len() > 1);
+        assert_eq!(
+            layout.rooms[0].center(),
+            layout.player_start,
+            "The first room is the player's tile"
+        );
+        for room in &layout.rooms {
+            for y in room.y1..room.y2 {
+                for x in room.x1..room.x2 {
+                    assert!(layout.tiles[y as usize][x as usize].is_walkable());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_smoothing_fills_isolated_floor() {
+        let mut tiles = vec![vec![TileType::Wall; 5]; 5];
+        tiles[2][2] = TileType::Floor;
+        let smoothed = smooth(&tiles);
+        assert_eq!(smoothed[2][2], TileType::Wall);
+        assert!(smoothed.iter().flatten().all(|&t| t == TileType::Wall));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    entity::position::Position,
+    game::config::MAX_ROOMS,
+    world::{
+        generator::{
+            MapGenerator, MapLayout, Room, carve_corridor, carve_room, far_apart, floor_regions,
+            keep_only, open_areas,
+        },
+        tile::TileType,
+    },
+};
+use rand::{Rng, RngCore, seq::SliceRandom};
+
+/// Where each new walker starts digging
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkerSpawn {
+    /// Every walker starts from the centre of the map, giving one dense hub
+    Centre,
+    /// Each walker starts on a random tile that has already been dug
+    RandomFloor,
+    /// Each walker starts anywhere and a corridor joins it to the centre,
+    /// spreading tunnels across the whole map
+    Anywhere,
+}
+
+/// Winding mine-like tunnels dug by random walkers
+///
+/// Walkers stumble one tile at a time in random directions, turning rock
+/// into floor, until enough of the map is open or every walker has had its
+/// turn.
+///
+/// ```text
+/// ##########################
+/// ###..######...############
+/// ##....####.....###...#####
+/// ###.....##.##....#.....###
+/// ####..........##...##..###
+/// ##########################
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrunkardsWalk {
+    /// Most walkers released before giving up on `coverage`
+    pub walkers: usize,
+    /// Steps each walker takes
+    pub lifetime: usize,
+    /// Share of the map to dig out before stopping
+    pub coverage: f64,
+    pub spawn: WalkerSpawn,
+}
+
+impl Default for DrunkardsWalk {
+    fn default() -> Self {
+        DrunkardsWalk {
+            walkers: 100,
+            lifetime: 400,
+            coverage: 0.35,
+            spawn: WalkerSpawn::RandomFloor,
+        }
+    }
+}
+
+impl DrunkardsWalk {
+    /// The default walk with walkers starting according to `spawn`
+    pub fn with_spawn(spawn: WalkerSpawn) -> Self {
+        DrunkardsWalk {
+            spawn,
+            ..DrunkardsWalk::default()
+        }
+    }
+
+    /// Lets one walker dig for `lifetime` steps, recording newly dug tiles
+    ///
+    /// The walker bounces off the two outermost rings of the map, so the outer
+    /// wall stays solid with a tile of rock inside it, as around generated rooms.
+    fn walk(
+        &self,
+        tiles: &mut [Vec<TileType>],
+        start: Position,
+        dug: &mut Vec<Position>,
+        rng: &mut dyn RngCore,
+    ) {
+        let width = tiles[0].len() as i32;
+        let height = tiles.len() as i32;
+        let mut pos = start;
+
+        for _ in 0..self.lifetime {
+            let (dx, dy) = *[(0, -1), (0, 1), (-1, 0), (1, 0)]
+                .choose(rng)
+                .expect("There are four directions");
+            let next = pos.translate(dx, dy);
+            if next.x < 2 || next.y < 2 || next.x >= width - 2 || next.y >= height - 2 {
+                continue;
+            }
+            pos = next;
+            let tile = &mut tiles[pos.y as usize][pos.x as usize];
+            if *tile == TileType::Wall {
+                *tile = TileType::Floor;
+                dug.push(pos);
+            }
+        }
+    }
+}
+
+impl MapGenerator for DrunkardsWalk {
+    fn name(&self) -> &'static str {
+        match self.spawn {
+            WalkerSpawn::Centre => "drunkard's walk from the centre",
+            WalkerSpawn::RandomFloor => "drunkard's walk from dug tiles",
+            WalkerSpawn::Anywhere => "drunkard's walk from anywhere",
+        }
+    }
+
+    fn generate(
+        &self,
+        width: usize,
+        height: usize,
+        _depth: u32,
+        rng: &mut dyn RngCore,
+    ) -> MapLayout {
+        let mut tiles = vec![vec![TileType::Wall; width]; height];
+        let centre = Position {
+            x: width as i32 / 2,
+            y: height as i32 / 2,
+        };
+        let target = ((width * height) as f64 * self.coverage) as usize;
+        let mut dug = vec![centre];
+        carve_room(&mut tiles, &Room::new(centre.x, centre.y, 1, 1));
+
+        for _ in 0..self.walkers {
+            if dug.len() >= target {
+                break;
+            }
+            let start = match self.spawn {
+                WalkerSpawn::Centre => centre,
+                WalkerSpawn::RandomFloor => *dug.choose(rng).expect("The centre is always dug"),
+                WalkerSpawn::Anywhere => {
+                    let start = Position {
+                        x: rng.gen_range(2..width as i32 - 2),
+                        y: rng.gen_range(2..height as i32 - 2),
+                    };
+                    carve_corridor(&mut tiles, start, centre, rng);
+                    start
+                }
+            };
+            self.walk(&mut tiles, start, &mut dug, rng);
+        }
+
+        // Corridors can open tiles that `dug` doesn't know about; count them too
+        let cave = floor_regions(&tiles).into_iter().next().unwrap_or(dug);
+        keep_only(&mut tiles, &cave);
+
+        let (player_start, stairs_position) = far_apart(&tiles, centre);
+        let rooms = open_areas(&tiles, player_start, &[stairs_position], MAX_ROOMS, rng);
+
+        MapLayout {
+            tiles,
+            rooms,
+            player_start,
+            stairs_position,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::config::{DUNGEON_HEIGHT, DUNGEON_WIDTH};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    const SPAWNS: [WalkerSpawn; 3] = [
+        WalkerSpawn::Centre,
+        WalkerSpawn::RandomFloor,
+        WalkerSpawn::Anywhere,
+    ];
+
+    fn floor_count(layout: &MapLayout) -> usize {
+        layout
+            .tiles
+            .iter()
+            .flatten()
+            .filter(|t| t.is_walkable())
+            .count()
+    }
+
+    #[test]
+    fn test_reaches_coverage() {
+        for spawn in SPAWNS {
+            let generator = DrunkardsWalk::with_spawn(spawn);
+            let mut rng = StdRng::seed_from_u64(1);
+            let layout = generator.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng);
+            let target = (DUNGEON_WIDTH * DUNGEON_HEIGHT) as f64 * generator.coverage;
+            assert!(floor_count(&layout) as f64 >= target, "{:?}", spawn);
+        }
+    }
+
+    #[test]
+    fn test_walker_count_limits_digging() {
+        let generator = DrunkardsWalk {
+            walkers: 1,
+            lifetime: 50,
+            ..DrunkardsWalk::default()
+        };
+        let mut rng = StdRng::seed_from_u64(1);
+        let layout = generator.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng);
+        // The centre plus at most one tile per step
+        assert!(floor_count(&layout) <= 51);
+    }
+
+    #[test]
+    fn test_tunnels_are_connected() {
+        for spawn in SPAWNS {
+            for seed in 0..5 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let generator = DrunkardsWalk::with_spawn(spawn);
+                let layout = generator.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng);
+                assert_eq!(floor_regions(&layout.tiles).len(), 1, "{:?}", spawn);
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use super::{Connectivity, MapLayout, ORTHOGONAL, Room, floor_regions, open_regions, tile_at};
+use crate::{
+    entity::position::Position,
+    game::config::{
+        CHASM_CHANCE, CHASM_MIN_DEPTH, LAVA_LAKE_CHANCE, LAVA_LAKE_MIN_DEPTH, MAX_DEPTH,
+        RIVER_CHANCE,
+    },
+    world::tile::TileType,
+};
+use rand::{Rng, RngCore, seq::SliceRandom};
+use std::ops::RangeInclusive;
+
+/// Number of tiles a lava lake or chasm grows to
+const POOL_SIZE: RangeInclusive<usize> = 6..=16;
+
+/// Rooms tried for a lava lake or chasm before giving up on it
+const POOL_ATTEMPTS: usize = 5;
+
+/// One step along a river: the deep tiles across it and the tiles just
+/// beyond them on either bank
+struct Crossing {
+    span: Vec<Position>,
+    banks: (Position, Position),
+}
+
+/// Lays hazard terrain over a generated floor: sometimes a river, and on
+/// deeper floors a lava lake or a chasm
+///
+/// Only plain floor is flooded, never the player start, the stairs or a room
+/// centre, and anything that would cut the floor apart is bridged or undone,
+/// so the layout stays as connected as the generator left it. That includes
+/// the way to the stairs, which never comes to need a secret door.
+pub fn lay_hazards(layout: &mut MapLayout, depth: u32, rng: &mut dyn RngCore) {
+    if rng.gen_bool(RIVER_CHANCE) {
+        lay_river(layout, rng);
+    }
+    if depth >= LAVA_LAKE_MIN_DEPTH && rng.gen_bool(LAVA_LAKE_CHANCE) {
+        lay_pool(layout, TileType::Lava, rng);
+    }
+    // Falling into the boss arena would skip its entrance, so the floor above
+    // it never gets a chasm
+    if depth >= CHASM_MIN_DEPTH && depth + 1 < MAX_DEPTH && rng.gen_bool(CHASM_CHANCE) {
+        lay_pool(layout, TileType::Chasm, rng);
+    }
+}
+
+/// Runs a river from one side of the map to the other: a channel of deep
+/// water between shallow banks, meandering a tile at a time
+///
+/// Wherever the channel cuts the floor in two, a bridge is thrown across it.
+/// If no bridge can rejoin the pieces the river is not laid at all.
+pub(crate) fn lay_river(layout: &mut MapLayout, rng: &mut dyn RngCore) {
+    let original = layout.tiles.clone();
+    let regions_before = floor_regions(&layout.tiles).len();
+    let keep = protected(layout);
+
+    let (width, height) = (layout.tiles[0].len() as i32, layout.tiles.len() as i32);
+    let horizontal = rng.gen_bool(0.5);
+    let (length, breadth) = if horizontal {
+        (width, height)
+    } else {
+        (height, width)
+    };
+    let at = |along: i32, across: i32| {
+        if horizontal {
+            Position {
+                x: along,
+                y: across,
+            }
+        } else {
+            Position {
+                x: across,
+                y: along,
+            }
+        }
+    };
+
+    // Half-widths of the deep channel and of the whole river
+    let deep = rng.gen_range(0..=1);
+    let half = deep + 1;
+    let mut centre = rng.gen_range(breadth / 4..breadth * 3 / 4);
+    let mut crossings = Vec::new();
+    for along in 1..length - 1 {
+        for offset in -half..=half {
+            let pos = at(along, centre + offset);
+            if can_flood(layout, &keep, pos) {
+                let water = if offset.abs() <= deep {
+                    TileType::DeepWater
+                } else {
+                    TileType::ShallowWater
+                };
+                layout.tiles[pos.y as usize][pos.x as usize] = water;
+            }
+        }
+        crossings.push(Crossing {
+            span: (-deep..=deep)
+                .map(|offset| at(along, centre + offset))
+                .collect(),
+            banks: (at(along, centre - half), at(along, centre + half)),
+        });
+        centre = (centre + rng.gen_range(-1..=1)).clamp(half + 1, breadth - half - 2);
+    }
+
+    // Bridges join the pieces a player can walk between without finding a
+    // secret door, so the way to the stairs is rejoined too
+    loop {
+        if still_connected(layout, regions_before) {
+            return;
+        }
+        let regions = open_regions(&layout.tiles);
+        let region_of = region_map(&layout.tiles, &regions);
+        let region_at = |pos: Position| region_of[pos.y as usize][pos.x as usize];
+        let bridges: Vec<&Crossing> = crossings
+            .iter()
+            .filter(|crossing| {
+                let tiles: Vec<Option<TileType>> = crossing
+                    .span
+                    .iter()
+                    .map(|&pos| tile_at(&layout.tiles, pos))
+                    .collect();
+                // A bridge has to replace some deep water, or laying it would
+                // join nothing and the loop would never end
+                tiles.contains(&Some(TileType::DeepWater))
+                    && tiles.iter().all(|t| {
+                        t.is_some_and(|t| {
+                            t == TileType::DeepWater || (t.is_passable() && !t.is_secret())
+                        })
+                    })
+            })
+            .filter(|crossing| {
+                let (a, b) = crossing.banks;
+                matches!((region_at(a), region_at(b)), (Some(a), Some(b)) if a != b)
+            })
+            .collect();
+
+        let Some(bridge) = bridges.choose(rng) else {
+            layout.tiles = original;
+            return;
+        };
+        for &pos in &bridge.span {
+            let tile = &mut layout.tiles[pos.y as usize][pos.x as usize];
+            if *tile == TileType::DeepWater {
+                *tile = TileType::Bridge;
+            }
+        }
+    }
+}
+
+/// Grows a lake of `tile` (lava, or the drop of a chasm) out from a spot in
+/// a random room, spilling into corridors and neighbouring caves
+///
+/// A lake that would cut the floor apart is undone and another room tried,
+/// up to `POOL_ATTEMPTS` times.
+pub(crate) fn lay_pool(layout: &mut MapLayout, tile: TileType, rng: &mut dyn RngCore) {
+    let keep = protected(layout);
+    let regions_before = floor_regions(&layout.tiles).len();
+
+    for _ in 0..POOL_ATTEMPTS {
+        let Some(room) = layout.rooms.choose(rng).copied() else {
+            return;
+        };
+        let start = Position {
+            x: rng.gen_range(room.x1..room.x2),
+            y: rng.gen_range(room.y1..room.y2),
+        };
+        if !can_flood(layout, &keep, start) {
+            continue;
+        }
+
+        let original = layout.tiles.clone();
+        let size = rng.gen_range(POOL_SIZE);
+        let mut pool = vec![start];
+        layout.tiles[start.y as usize][start.x as usize] = tile;
+        for _ in 0..size * 4 {
+            if pool.len() >= size {
+                break;
+            }
+            let (dx, dy) = *ORTHOGONAL.choose(rng).expect("ORTHOGONAL is not empty");
+            let next = pool
+                .choose(rng)
+                .expect("pool is not empty")
+                .translate(dx, dy);
+            if can_flood(layout, &keep, next) {
+                layout.tiles[next.y as usize][next.x as usize] = tile;
+                pool.push(next);
+            }
+        }
+
+        if still_connected(layout, regions_before) {
+            return;
+        }
+        layout.tiles = original;
+    }
+}
+
+/// Tiles that must stay plain floor: the player start, the stairs and the
+/// room centres that spawning relies on
+fn protected(layout: &MapLayout) -> Vec<Position> {
+    let mut keep: Vec<Position> = layout.rooms.iter().map(Room::center).collect();
+    keep.extend([layout.player_start, layout.stairs_position]);
+    keep
+}
+
+/// Whether the floor is in no more pieces than the `regions_before` it had,
+/// and the stairs can still be reached without finding a secret door
+fn still_connected(layout: &MapLayout, regions_before: usize) -> bool {
+    floor_regions(&layout.tiles).len() <= regions_before
+        && Connectivity::new(&layout.tiles, layout.player_start)
+            .reachable_without_secrets(layout.stairs_position)
+}
+
+fn can_flood(layout: &MapLayout, keep: &[Position], pos: Position) -> bool {
+    tile_at(&layout.tiles, pos) == Some(TileType::Floor) && !keep.contains(&pos)
+}
+
+/// The index into `regions` of every tile, `None` for tiles in no region
+fn region_map(tiles: &[Vec<TileType>], regions: &[Vec<Position>]) -> Vec<Vec<Option<usize>>> {
+    let mut map = vec![vec![None; tiles[0].len()]; tiles.len()];
+    for (index, region) in regions.iter().enumerate() {
+        for pos in region {
+            map[pos.y as usize][pos.x as usize] = Some(index);
+        }
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::config::{DUNGEON_HEIGHT, DUNGEON_WIDTH},
+        world::generator::{MapGenerator, RoomsAndCorridors, all_generators},
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn count(layout: &MapLayout, tile: TileType) -> usize {
+        layout
+            .tiles
+            .iter()
+            .flatten()
+            .filter(|&&t| t == tile)
+            .count()
+    }
+
+    #[test]
+    fn test_hazards_keep_floor_connected() {
+        for generator in all_generators() {
+            for seed in 0..10 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut layout = generator.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 6, &mut rng);
+                let regions = floor_regions(&layout.tiles).len();
+                let keep = protected(&layout);
+
+                lay_river(&mut layout, &mut rng);
+                lay_pool(&mut layout, TileType::Lava, &mut rng);
+                lay_pool(&mut layout, TileType::Chasm, &mut rng);
+
+                let name = generator.name();
+                assert!(still_connected(&layout, regions), "{}", name);
+                for pos in keep {
+                    assert!(layout.tiles[pos.y as usize][pos.x as usize].is_walkable());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rivers_are_bridged() {
+        let bridged = (0..20).any(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut layout = RoomsAndCorridors.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng);
+            lay_river(&mut layout, &mut rng);
+            count(&layout, TileType::DeepWater) > 0 && count(&layout, TileType::Bridge) > 0
+        });
+        assert!(bridged);
+    }
+
+    #[test]
+    fn test_pools_flood_only_floor() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut layout = RoomsAndCorridors.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng);
+        let walls = count(&layout, TileType::Wall);
+
+        lay_pool(&mut layout, TileType::Lava, &mut rng);
+
+        assert_eq!(count(&layout, TileType::Wall), walls);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    game::config::{MAX_ROOM_SIZE, MAX_ROOMS, MIN_ROOM_SIZE, MIN_ROOMS},
+    world::{
+        generator::{
+            Bsp, MapGenerator, MapLayout, Room, carve_corridor, carve_room, hide_corridor_ends,
+            place_doors,
+        },
+        tile::TileType,
+    },
+};
+use rand::{Rng, RngCore};
+
+/// The classic layout: rectangular rooms placed at random, each joined to the
+/// previous one by an L-shaped corridor
+///
+/// The player starts in the first room and the stairs are in the last.
+/// Corridors enter rooms through doors, some of them locked, and some
+/// corridors end in a secret door. Should the attempts run out before
+/// `MIN_ROOMS` rooms fit, the floor is laid out by [`Bsp`] instead.
+pub struct RoomsAndCorridors;
+
+impl MapGenerator for RoomsAndCorridors {
+    fn name(&self) -> &'static str {
+        "rooms and corridors"
+    }
+
+    fn generate(
+        &self,
+        width: usize,
+        height: usize,
+        depth: u32,
+        rng: &mut dyn RngCore,
+    ) -> MapLayout {
+        // Initialize all tiles as walls
+        let mut tiles = vec![vec![TileType::Wall; width]; height];
+        let mut rooms = Vec::new();
+        let mut corridors = Vec::new();
+
+        // Determine desired number of rooms (may not be reached due to MAX_ATTEMPTS)
+        let desired_room_count = rng.gen_range(MIN_ROOMS..=MAX_ROOMS);
+        let mut attempts = 0;
+        const MAX_ATTEMPTS: usize = 200;
+
+        // Try to place rooms
+        while rooms.len() < desired_room_count && attempts < MAX_ATTEMPTS {
+            let room_width = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE);
+            let room_height = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE);
+            let x = rng.gen_range(1..(width as i32 - room_width - 1));
+            let y = rng.gen_range(1..(height as i32 - room_height - 1));
+
+            let new_room = Room::new(x, y, room_width, room_height);
+
+            // Check if this room intersects with any existing room
+            let intersects = rooms.iter().any(|r: &Room| r.intersects(&new_room));
+
+            if !intersects {
+                // Carve out the room
+                carve_room(&mut tiles, &new_room);
+
+                // Connect to previous room with corridor
+                if !rooms.is_empty() {
+                    let prev_center = rooms.last().unwrap().center();
+                    let new_center = new_room.center();
+                    corridors.push(carve_corridor(&mut tiles, prev_center, new_center, rng));
+                }
+
+                rooms.push(new_room);
+            }
+
+            attempts += 1;
+        }
+
+        // Too few rooms make for a thin floor; the partition always fits them
+        if rooms.len() < MIN_ROOMS {
+            return Bsp.generate(width, height, depth, rng);
+        }
+
+        // Determine player start and stairs positions
+        let player_start = rooms[0].center();
+        let stairs_position = rooms[rooms.len() - 1].center();
+
+        place_doors(&mut tiles, &rooms, &[player_start, stairs_position], rng);
+        hide_corridor_ends(
+            &mut tiles,
+            &rooms,
+            &corridors,
+            player_start,
+            stairs_position,
+            rng,
+        );
+
+        MapLayout {
+            tiles,
+            rooms,
+            player_start,
+            stairs_position,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::config::{DUNGEON_HEIGHT, DUNGEON_WIDTH};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn layout() -> MapLayout {
+        let mut rng = StdRng::seed_from_u64(42);
+        RoomsAndCorridors.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng)
+    }
+
+    #[test]
+    fn test_dungeon_has_rooms() {
+        let layout = layout();
+        assert!(!layout.rooms.is_empty());
+        assert!(layout.rooms.len() >= MIN_ROOMS);
+        assert!(layout.rooms.len() <= MAX_ROOMS);
+    }
+
+    #[test]
+    fn test_too_few_rooms_falls_back_to_bsp() {
+        // On a map this cramped, rejection sampling with seed 0 fits only 5 rooms
+        let mut rng = StdRng::seed_from_u64(0);
+        let layout = RoomsAndCorridors.generate(40, 30, 1, &mut rng);
+        assert!(layout.rooms.len() >= MIN_ROOMS);
+        assert_eq!(layout.player_start, layout.rooms[0].center());
+        assert_eq!(
+            layout.stairs_position,
+            layout.rooms[layout.rooms.len() - 1].center()
+        );
+    }
+
+    #[test]
+    fn test_rooms_do_not_overlap() {
+        let layout = layout();
+        for (i, room1) in layout.rooms.iter().enumerate() {
+            for room2 in layout.rooms.iter().skip(i + 1) {
+                // Check actual overlap (not just adjacency)
+                let overlaps = room1.x1 < room2.x2
+                    && room1.x2 > room2.x1
+                    && room1.y1 < room2.y2
+                    && room1.y2 > room2.y1;
+                assert!(!overlaps, "Rooms {:?} and {:?} overlap", room1, room2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stairs_in_last_room() {
+        let layout = layout();
+        let last_room = layout.rooms.last().unwrap();
+        let stairs = layout.stairs_position;
+        assert!(
+            stairs.x >= last_room.x1
+                && stairs.x < last_room.x2
+                && stairs.y >= last_room.y1
+                && stairs.y < last_room.y2,
+            "Stairs should be in last room"
+        );
+    }
+
+    #[test]
+    fn test_player_start_in_first_room() {
+        let layout = layout();
+        let first_room = &layout.rooms[0];
+        let start = layout.player_start;
+        assert!(
+            start.x >= first_room.x1
+                && start.x < first_room.x2
+                && start.y >= first_room.y1
+                && start.y < first_room.y2,
+            "Player should start in first room"
+        );
+    }
+}
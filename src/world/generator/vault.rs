@@ -0,0 +1,563 @@
+use crate::{
+    entity::{monster::MonsterKind, position::Position},
+    game::config::{MAX_VAULTS_PER_FLOOR, VAULT_CHANCE},
+    item::ItemKind,
+    world::{
+        generator::{MapLayout, ORTHOGONAL, Room, tile_at},
+        tile::TileType,
+    },
+};
+use anyhow::{Context, Result, bail};
+use rand::{Rng, RngCore, seq::SliceRandom};
+use std::collections::{HashMap, VecDeque};
+
+/// The vaults that ship with the game
+const BUILTIN_VAULTS: &str = include_str!("vaults.txt");
+
+/// Random spots tried for a vault before giving up on it
+const PLACEMENT_ATTEMPTS: usize = 50;
+
+/// Something placed on a vault tile when the floor is populated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spawn {
+    /// A monster; `None` picks one suited to the depth
+    Monster(Option<MonsterKind>),
+    /// An item; `None` picks one suited to the depth
+    Item(Option<ItemKind>),
+}
+
+/// What a template character stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub tile: TileType,
+    pub spawn: Option<Spawn>,
+}
+
+/// A hand-designed room, such as a shrine or a monster den
+#[derive(Debug, Clone, PartialEq)]
+pub struct VaultTemplate {
+    pub name: String,
+    /// Shallowest floor the vault may appear on
+    pub min_depth: u32,
+    /// Rows of cells; `None` leaves the generated map as it is
+    pub cells: Vec<Vec<Option<Cell>>>,
+}
+
+impl VaultTemplate {
+    /// The cells turned clockwise by `quarter_turns`, then mirrored left to
+    /// right if `mirror` is set
+    pub fn transformed(&self, quarter_turns: u8, mirror: bool) -> Vec<Vec<Option<Cell>>> {
+        let mut cells = self.cells.clone();
+        for _ in 0..quarter_turns % 4 {
+            let height = cells.len();
+            let width = cells[0].len();
+            cells = (0..width)
+                .map(|x| (0..height).rev().map(|y| cells[y][x]).collect())
+                .collect();
+        }
+        if mirror {
+            for row in &mut cells {
+                row.reverse();
+            }
+        }
+        cells
+    }
+}
+
+/// The built-in vaults
+pub fn builtin_vaults() -> Vec<VaultTemplate> {
+    parse_vaults(BUILTIN_VAULTS).expect("The built-in vaults are valid")
+}
+
+/// Parses vault templates from text
+///
+/// ```text
+/// // Comments start with two slashes
+/// name: Shrine            starts a new vault
+/// min_depth: 2            optional, 1 if left out
+/// legend: o = item Ofuda  optional, adds to or overrides the default legend
+/// map:                    the rows follow, up to the next blank line
+/// #####
+/// #.o.#
+/// ##.##
+/// ```
+///
+/// The default legend is `#` wall, `.` floor, `+` door, `m` a monster for
+/// the depth, `i` an item for the depth, and space for rock that is left as
+/// generated. A legend entry is `wall`, `floor`, `door`, `locked door`,
+/// `rock`, `monster` or `item`, the last two optionally followed by a
+/// monster or item name. Floor and doors on the edge of a map are its
+/// entrances; every locked door gets a key somewhere else on the floor.
+pub fn parse_vaults(text: &str) -> Result<Vec<VaultTemplate>> {
+    let mut vaults = Vec::new();
+    let mut current: Option<VaultBuilder> = None;
+    let mut in_map = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        if in_map {
+            if line.trim().is_empty() {
+                in_map = false;
+            } else if let Some(vault) = &mut current {
+                vault.rows.push(line.trim_end().to_string());
+            }
+            continue;
+        }
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            bail!("Line {}: expected 'key: value'", line_number);
+        };
+        let value = value.trim();
+
+        if key == "name" {
+            if let Some(vault) = current.take() {
+                vaults.push(vault.build()?);
+            }
+            current = Some(VaultBuilder::new(value));
+            continue;
+        }
+        let Some(vault) = &mut current else {
+            bail!("Line {}: '{}' before the first 'name'", line_number, key);
+        };
+        match key {
+            "min_depth" => {
+                vault.min_depth = value
+                    .parse()
+                    .with_context(|| format!("Line {}: invalid depth '{}'", line_number, value))?;
+            }
+            "legend" => {
+                let (symbol, cell) =
+                    parse_legend(value).with_context(|| format!("Line {}", line_number))?;
+                vault.legend.insert(symbol, cell);
+            }
+            "map" => in_map = true,
+            _ => bail!("Line {}: unknown key '{}'", line_number, key),
+        }
+    }
+
+    if let Some(vault) = current {
+        vaults.push(vault.build()?);
+    }
+    Ok(vaults)
+}
+
+/// A vault while it is being read
+struct VaultBuilder {
+    name: String,
+    min_depth: u32,
+    legend: HashMap<char, Option<Cell>>,
+    rows: Vec<String>,
+}
+
+impl VaultBuilder {
+    fn new(name: &str) -> Self {
+        let floor = |spawn| {
+            Some(Cell {
+                tile: TileType::Floor,
+                spawn,
+            })
+        };
+        let legend = HashMap::from([
+            (' ', None),
+            (
+                '#',
+                Some(Cell {
+                    tile: TileType::Wall,
+                    spawn: None,
+                }),
+            ),
+            ('.', floor(None)),
+            (
+                '+',
+                Some(Cell {
+                    tile: TileType::DoorClosed,
+                    spawn: None,
+                }),
+            ),
+            ('m', floor(Some(Spawn::Monster(None)))),
+            ('i', floor(Some(Spawn::Item(None)))),
+        ]);
+        VaultBuilder {
+            name: name.to_string(),
+            min_depth: 1,
+            legend,
+            rows: Vec::new(),
+        }
+    }
+
+    fn build(self) -> Result<VaultTemplate> {
+        let width = self.rows.iter().map(|row| row.chars().count()).max();
+        let Some(width) = width.filter(|&w| w > 0) else {
+            bail!("Vault '{}' has no map", self.name);
+        };
+
+        let mut cells = Vec::new();
+        for row in &self.rows {
+            // Editors strip trailing spaces, so short rows are padded with rock
+            let symbols = row.chars().chain(std::iter::repeat(' ')).take(width);
+            let row = symbols
+                .map(|symbol| {
+                    self.legend.get(&symbol).copied().with_context(|| {
+                        format!(
+                            "Vault '{}' uses '{}', which is not in the legend",
+                            self.name, symbol
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            cells.push(row);
+        }
+
+        let vault = VaultTemplate {
+            name: self.name,
+            min_depth: self.min_depth,
+            cells,
+        };
+        if entrances(&vault.cells).is_empty() {
+            bail!("Vault '{}' has no entrance on its edge", vault.name);
+        }
+        Ok(vault)
+    }
+}
+
+/// Parses a legend entry such as `O = monster Oni`
+fn parse_legend(entry: &str) -> Result<(char, Option<Cell>)> {
+    let (symbol, meaning) = entry
+        .split_once('=')
+        .context("Expected a legend entry like 'o = item Ofuda'")?;
+    let mut symbols = symbol.trim().chars();
+    let (Some(symbol), None) = (symbols.next(), symbols.next()) else {
+        bail!("Legend symbol '{}' must be one character", symbol.trim());
+    };
+
+    let meaning = meaning.trim();
+    let door = |tile| Some(Cell { tile, spawn: None });
+    match meaning {
+        "door" => return Ok((symbol, door(TileType::DoorClosed))),
+        "locked door" => return Ok((symbol, door(TileType::DoorLocked))),
+        _ => {}
+    }
+    let (kind, name) = match meaning.split_once(' ') {
+        Some((kind, name)) => (kind, Some(name.trim())),
+        None => (meaning, None),
+    };
+    let floor_with = |spawn| Cell {
+        tile: TileType::Floor,
+        spawn: Some(spawn),
+    };
+    let cell = match (kind, name) {
+        ("rock", None) => None,
+        ("wall", None) => Some(Cell {
+            tile: TileType::Wall,
+            spawn: None,
+        }),
+        ("floor", None) => Some(Cell {
+            tile: TileType::Floor,
+            spawn: None,
+        }),
+        ("monster", None) => Some(floor_with(Spawn::Monster(None))),
+        ("monster", Some(name)) => {
+            let monster = MonsterKind::ALL
+                .into_iter()
+                .find(|m| m.name().eq_ignore_ascii_case(name))
+                .with_context(|| format!("Unknown monster '{}'", name))?;
+            Some(floor_with(Spawn::Monster(Some(monster))))
+        }
+        ("item", None) => Some(floor_with(Spawn::Item(None))),
+        ("item", Some(name)) => {
+            let item = ItemKind::ALL
+                .into_iter()
+                .find(|i| i.name().eq_ignore_ascii_case(name))
+                .with_context(|| format!("Unknown item '{}'", name))?;
+            Some(floor_with(Spawn::Item(Some(item))))
+        }
+        _ => bail!("Unknown legend meaning '{}'", meaning),
+    };
+    Ok((symbol, cell))
+}
+
+/// Passable cells on the outer edge of a template, as (column, row)
+fn entrances(cells: &[Vec<Option<Cell>>]) -> Vec<(usize, usize)> {
+    let height = cells.len();
+    let width = cells.first().map_or(0, |row| row.len());
+    let mut found = Vec::new();
+    for (y, row) in cells.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            let on_edge = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+            if on_edge && cell.is_some_and(|c| c.tile.is_passable()) {
+                found.push((x, y));
+            }
+        }
+    }
+    found
+}
+
+/// Stamps vaults into `layout` and returns what should spawn in them
+///
+/// Up to `MAX_VAULTS_PER_FLOOR` vaults are tried, each with `VAULT_CHANCE`,
+/// picked from the templates allowed at `depth`.
+pub fn place_vaults(
+    layout: &mut MapLayout,
+    templates: &[VaultTemplate],
+    depth: u32,
+    rng: &mut dyn RngCore,
+) -> Vec<(Position, Spawn)> {
+    let allowed: Vec<&VaultTemplate> = templates.iter().filter(|t| t.min_depth <= depth).collect();
+    let mut placed = Vec::new();
+    let mut spawns = Vec::new();
+
+    for _ in 0..MAX_VAULTS_PER_FLOOR {
+        if !rng.gen_bool(VAULT_CHANCE) {
+            continue;
+        }
+        let Some(&template) = allowed.choose(rng) else {
+            break;
+        };
+        if let Some(vault_spawns) = place_vault(layout, template, &mut placed, rng) {
+            spawns.extend(vault_spawns);
+        }
+    }
+    spawns
+}
+
+/// Stamps one vault, turned and mirrored at random, into solid rock and
+/// tunnels each of its entrances to the nearest floor
+///
+/// The vault keeps a tile of rock between itself and anything already dug,
+/// so it never breaks into a room by accident. Returns `None` when there is
+/// no room for it. The area it covers is added to `placed`.
+pub fn place_vault(
+    layout: &mut MapLayout,
+    template: &VaultTemplate,
+    placed: &mut Vec<Room>,
+    rng: &mut dyn RngCore,
+) -> Option<Vec<(Position, Spawn)>> {
+    let cells = template.transformed(rng.gen_range(0..4), rng.gen_bool(0.5));
+    let area = free_area(
+        &layout.tiles,
+        cells[0].len() as i32,
+        cells.len() as i32,
+        rng,
+    )?;
+    placed.push(area);
+
+    let mut spawns = Vec::new();
+    for (dy, row) in cells.iter().enumerate() {
+        for (dx, cell) in row.iter().enumerate() {
+            let Some(cell) = cell else {
+                continue;
+            };
+            let pos = Position {
+                x: area.x1 + dx as i32,
+                y: area.y1 + dy as i32,
+            };
+            layout.tiles[pos.y as usize][pos.x as usize] = cell.tile;
+            if let Some(spawn) = cell.spawn {
+                spawns.push((pos, spawn));
+            }
+        }
+    }
+
+    for (dx, dy) in entrances(&cells) {
+        let entrance = Position {
+            x: area.x1 + dx as i32,
+            y: area.y1 + dy as i32,
+        };
+        tunnel(&mut layout.tiles, entrance, placed);
+    }
+    Some(spawns)
+}
+
+/// A `width` x `height` area of untouched rock with a ring of rock around
+/// it, clear of the map's outer wall
+fn free_area(
+    tiles: &[Vec<TileType>],
+    width: i32,
+    height: i32,
+    rng: &mut dyn RngCore,
+) -> Option<Room> {
+    let map_width = tiles[0].len() as i32;
+    let map_height = tiles.len() as i32;
+    if width + 4 > map_width || height + 4 > map_height {
+        return None;
+    }
+
+    for _ in 0..PLACEMENT_ATTEMPTS {
+        let area = Room::new(
+            rng.gen_range(2..=map_width - width - 2),
+            rng.gen_range(2..=map_height - height - 2),
+            width,
+            height,
+        );
+        let solid = (area.y1 - 1..=area.y2).all(|y| {
+            (area.x1 - 1..=area.x2)
+                .all(|x| tile_at(tiles, Position { x, y }) == Some(TileType::Wall))
+        });
+        if solid {
+            return Some(area);
+        }
+    }
+    None
+}
+
+/// Digs the shortest tunnel from `entrance` to the nearest passable tile
+/// outside every vault in `vaults`, without passing through any vault
+fn tunnel(tiles: &mut [Vec<TileType>], entrance: Position, vaults: &[Room]) {
+    let width = tiles[0].len() as i32;
+    let height = tiles.len() as i32;
+    let mut came_from: Vec<Vec<Option<Position>>> =
+        vec![vec![None; width as usize]; height as usize];
+    let mut queue = VecDeque::from([entrance]);
+    came_from[entrance.y as usize][entrance.x as usize] = Some(entrance);
+
+    while let Some(pos) = queue.pop_front() {
+        let outside = !vaults.iter().any(|vault| vault.contains(pos));
+        if outside && tiles[pos.y as usize][pos.x as usize].is_passable() {
+            let mut step = pos;
+            while step != entrance {
+                tiles[step.y as usize][step.x as usize] = TileType::Floor;
+                step = came_from[step.y as usize][step.x as usize]
+                    .expect("Visited tiles have a parent");
+            }
+            return;
+        }
+
+        for (dx, dy) in ORTHOGONAL {
+            let next = pos.translate(dx, dy);
+            let inside_map =
+                next.x >= 1 && next.y >= 1 && next.x < width - 1 && next.y < height - 1;
+            if !inside_map
+                || came_from[next.y as usize][next.x as usize].is_some()
+                || vaults.iter().any(|vault| vault.contains(next))
+            {
+                continue;
+            }
+            came_from[next.y as usize][next.x as usize] = Some(pos);
+            queue.push_back(next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::config::{DUNGEON_HEIGHT, DUNGEON_WIDTH},
+        world::generator::{MapGenerator, RoomsAndCorridors, floor_regions},
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    const SMALL: &str = "
+name: Alcove
+legend: K = monster kappa
+map:
+###
+#K#
+#.
+";
+
+    fn walkable(cells: &[Vec<Option<Cell>>]) -> Vec<Vec<TileType>> {
+        cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.map_or(TileType::Wall, |c| c.tile))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_vaults_are_valid() {
+        let vaults = builtin_vaults();
+        assert!(vaults.len() >= 4);
+        for vault in &vaults {
+            assert_eq!(
+                floor_regions(&walkable(&vault.cells)).len(),
+                1,
+                "{}: every walkable tile should be reachable from the entrances",
+                vault.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_legend_and_padding() {
+        let vaults = parse_vaults(SMALL).unwrap();
+        assert_eq!(vaults.len(), 1);
+        let vault = &vaults[0];
+        assert_eq!(vault.name, "Alcove");
+        assert_eq!(vault.min_depth, 1);
+        assert_eq!(
+            vault.cells[1][1].unwrap().spawn,
+            Some(Spawn::Monster(Some(MonsterKind::Kappa)))
+        );
+        // The short last row is padded with rock
+        assert_eq!(vault.cells[2].len(), 3);
+        assert_eq!(vault.cells[2][2], None);
+        assert_eq!(entrances(&vault.cells), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn test_parse_doors() {
+        let text = "name: Gate\nlegend: L = locked door\nmap:\n#+#\n#.#\n#L#\n";
+        let vault = &parse_vaults(text).unwrap()[0];
+        assert_eq!(vault.cells[0][1].unwrap().tile, TileType::DoorClosed);
+        assert_eq!(vault.cells[2][1].unwrap().tile, TileType::DoorLocked);
+        assert_eq!(entrances(&vault.cells), vec![(1, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let unknown_symbol = "name: Bad\nmap:\n#?#\n#.#\n";
+        assert!(parse_vaults(unknown_symbol).is_err());
+        let unknown_monster = "name: Bad\nlegend: X = monster Dragon\nmap:\n#X#\n#.#\n";
+        assert!(parse_vaults(unknown_monster).is_err());
+        let no_entrance = "name: Bad\nmap:\n###\n#.#\n###\n";
+        assert!(parse_vaults(no_entrance).is_err());
+        let no_name = "map:\n#.#\n";
+        assert!(parse_vaults(no_name).is_err());
+    }
+
+    #[test]
+    fn test_transformations() {
+        let vault = &parse_vaults(SMALL).unwrap()[0];
+        let turned = vault.transformed(1, false);
+        assert_eq!((turned.len(), turned[0].len()), (3, 3));
+        // The entrance at the bottom ends up on the left after a clockwise turn
+        assert_eq!(entrances(&turned), vec![(0, 1)]);
+        assert_eq!(entrances(&vault.transformed(1, true)), vec![(2, 1)]);
+        assert_eq!(vault.transformed(4, false), vault.cells);
+        let mut upside_down = vault.cells.clone();
+        upside_down.reverse();
+        upside_down.iter_mut().for_each(|row| row.reverse());
+        assert_eq!(vault.transformed(2, false), upside_down);
+    }
+
+    #[test]
+    fn test_placed_vault_is_connected() {
+        for vault in builtin_vaults() {
+            let mut rng = StdRng::seed_from_u64(3);
+            let mut layout = RoomsAndCorridors.generate(DUNGEON_WIDTH, DUNGEON_HEIGHT, 1, &mut rng);
+            let rooms = layout.rooms.clone();
+            let mut placed = Vec::new();
+            let spawns = place_vault(&mut layout, &vault, &mut placed, &mut rng)
+                .unwrap_or_else(|| panic!("{} found no free area", vault.name));
+
+            assert_eq!(floor_regions(&layout.tiles).len(), 1, "{}", vault.name);
+            let vault_area = placed[0];
+            for room in &rooms {
+                let overlaps = room.x1 < vault_area.x2
+                    && room.x2 > vault_area.x1
+                    && room.y1 < vault_area.y2
+                    && room.y2 > vault_area.y1;
+                assert!(!overlaps, "{} was stamped over {:?}", vault.name, room);
+            }
+            assert!(spawns.iter().all(|(pos, _)| vault_area.contains(*pos)));
+        }
+    }
+}
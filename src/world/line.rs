@@ -0,0 +1,78 @@
+use crate::entity::position::Position;
+
+/// Returns the tiles on a straight line from `from` to `to` (Bresenham)
+///
+/// The starting tile is excluded and the end tile is included, so the result
+/// is the path a projectile would travel:
+///
+/// ```text
+/// @ . .          from = @, to = x
+///     . . .      line = [., ., ., ., ., x]
+///         . x
+/// ```
+pub fn line(from: Position, to: Position) -> Vec<Position> {
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let sx = if from.x < to.x { 1 } else { -1 };
+    let sy = if from.y < to.y { 1 } else { -1 };
+
+    let mut points = Vec::new();
+    let mut err = dx + dy;
+    let mut current = from;
+
+    while current != to {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            current.x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            current.y += sy;
+        }
+        points.push(current);
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_to_self_is_empty() {
+        let p = Position { x: 3, y: 3 };
+        assert!(line(p, p).is_empty());
+    }
+
+    #[test]
+    fn test_horizontal_line() {
+        let points = line(Position { x: 0, y: 0 }, Position { x: 3, y: 0 });
+        assert_eq!(
+            points,
+            vec![
+                Position { x: 1, y: 0 },
+                Position { x: 2, y: 0 },
+                Position { x: 3, y: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagonal_line() {
+        let points = line(Position { x: 0, y: 0 }, Position { x: -2, y: 2 });
+        assert_eq!(
+            points,
+            vec![Position { x: -1, y: 1 }, Position { x: -2, y: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_line_ends_at_target() {
+        let to = Position { x: 7, y: -3 };
+        let points = line(Position { x: 0, y: 0 }, to);
+        assert_eq!(points.last(), Some(&to));
+        assert_eq!(points.len(), 7);
+    }
+}
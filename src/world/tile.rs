@@ -1,20 +1,120 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TileType {
     Wall,
     Floor,
     StairsDown,
+    StairsUp,
+    /// Blocks sight; bumping into it opens it
+    DoorClosed,
+    DoorOpen,
+    /// Like a closed door, but opening it uses up a key
+    DoorLocked,
+    /// Looks and acts like wall until found by searching, when it becomes a
+    /// closed door
+    DoorSecret,
+    /// Slows walkers down and puts out flames
+    ShallowWater,
+    /// Has to be swum; walkers who stay in too long drown
+    DeepWater,
+    /// Burns whoever steps in without wings
+    Lava,
+    /// Drops walkers onto the floor below
+    Chasm,
+    /// Walkable span across water, lava or a chasm
+    Bridge,
+}
+
+/// How a creature gets about, which decides the terrain it can cross
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// On foot: wades through shallow water and swims in deep water
+    Walk,
+    /// At home in water of any depth
+    Swim,
+    /// Flies over water, lava and chasms alike
+    Fly,
 }
 
 impl TileType {
+    /// Whether a creature on foot can stand on the tile without coming to harm
+    ///
+    /// This is what spawning, teleporting and floor connectivity go by; for
+    /// the full picture per creature see `move_cost` and `is_hazardous_for`.
     pub fn is_walkable(&self) -> bool {
-        matches!(self, TileType::Floor | TileType::StairsDown)
+        self.move_cost(Movement::Walk).is_some() && !self.is_hazardous_for(Movement::Walk)
+    }
+
+    /// Turns it takes a creature moving by `movement` to enter the tile, or
+    /// `None` if it can't enter it at all
+    ///
+    /// ```text
+    ///                   Walk  Swim  Fly
+    /// floor, bridge       1     1    1
+    /// shallow water       2     1    1
+    /// deep water          2     1    1
+    /// lava, chasm         1     1    1
+    /// wall, shut door     -     -    -
+    /// ```
+    pub fn move_cost(&self, movement: Movement) -> Option<u32> {
+        match self {
+            TileType::Wall | TileType::DoorClosed | TileType::DoorLocked | TileType::DoorSecret => {
+                None
+            }
+            TileType::ShallowWater | TileType::DeepWater if movement == Movement::Walk => Some(2),
+            _ => Some(1),
+        }
+    }
+
+    /// Whether entering the tile hurts a creature moving by `movement`
+    ///
+    /// Monsters never step onto such tiles; the player can, at their peril.
+    pub fn is_hazardous_for(&self, movement: Movement) -> bool {
+        match self {
+            TileType::DeepWater => movement == Movement::Walk,
+            TileType::Lava | TileType::Chasm => movement != Movement::Fly,
+            _ => false,
+        }
+    }
+
+    pub fn is_door(&self) -> bool {
+        matches!(
+            self,
+            TileType::DoorClosed | TileType::DoorOpen | TileType::DoorLocked | TileType::DoorSecret
+        )
+    }
+
+    pub fn is_secret(&self) -> bool {
+        *self == TileType::DoorSecret
+    }
+
+    /// Whether the tile can be got through, possibly after opening or
+    /// unlocking a door (used to check that floors are connected)
+    pub fn is_passable(&self) -> bool {
+        self.is_walkable() || self.is_door()
+    }
+
+    /// Whether this tile stops line of sight (used by field-of-view)
+    pub fn blocks_sight(&self) -> bool {
+        matches!(
+            self,
+            TileType::Wall | TileType::DoorClosed | TileType::DoorLocked | TileType::DoorSecret
+        )
     }
 
     pub fn to_char(&self) -> char {
         match self {
-            TileType::Wall => '#',
+            TileType::Wall | TileType::DoorSecret => '#',
             TileType::Floor => '.',
             TileType::StairsDown => '>',
+            TileType::StairsUp => '<',
+            TileType::DoorClosed | TileType::DoorLocked => '+',
+            TileType::DoorOpen => '\'',
+            TileType::ShallowWater => '~',
+            TileType::DeepWater | TileType::Lava => '≈',
+            TileType::Chasm => ':',
+            TileType::Bridge => '=',
         }
     }
 }
@@ -36,6 +136,62 @@ mod tests {
     #[test]
     fn test_stairs_walkable() {
         assert!(TileType::StairsDown.is_walkable());
+        assert!(TileType::StairsUp.is_walkable());
+    }
+
+    #[test]
+    fn test_doors() {
+        assert!(!TileType::DoorClosed.is_walkable());
+        assert!(!TileType::DoorLocked.is_walkable());
+        assert!(TileType::DoorOpen.is_walkable());
+        assert!(TileType::DoorClosed.blocks_sight());
+        assert!(TileType::DoorLocked.blocks_sight());
+        assert!(!TileType::DoorOpen.blocks_sight());
+        assert!(TileType::DoorLocked.is_passable());
+        assert!(!TileType::Wall.is_passable());
+    }
+
+    #[test]
+    fn test_secret_door_passes_for_wall() {
+        let secret = TileType::DoorSecret;
+        assert_eq!(secret.to_char(), TileType::Wall.to_char());
+        assert!(secret.blocks_sight());
+        assert!(!secret.is_walkable());
+        assert_eq!(secret.move_cost(Movement::Fly), None);
+        // Still counts as a way through when checking connectivity
+        assert!(secret.is_passable());
+    }
+
+    #[test]
+    fn test_movement_costs() {
+        assert_eq!(TileType::Floor.move_cost(Movement::Walk), Some(1));
+        assert_eq!(TileType::ShallowWater.move_cost(Movement::Walk), Some(2));
+        assert_eq!(TileType::DeepWater.move_cost(Movement::Swim), Some(1));
+        assert_eq!(TileType::Wall.move_cost(Movement::Fly), None);
+        assert_eq!(TileType::DoorClosed.move_cost(Movement::Walk), None);
+    }
+
+    #[test]
+    fn test_hazards_depend_on_movement() {
+        assert!(TileType::DeepWater.is_hazardous_for(Movement::Walk));
+        assert!(!TileType::DeepWater.is_hazardous_for(Movement::Swim));
+        assert!(TileType::Lava.is_hazardous_for(Movement::Swim));
+        assert!(!TileType::Chasm.is_hazardous_for(Movement::Fly));
+
+        // Walkable means safe on foot, so only wading and bridges count
+        assert!(TileType::ShallowWater.is_walkable());
+        assert!(TileType::Bridge.is_walkable());
+        assert!(!TileType::DeepWater.is_walkable());
+        assert!(!TileType::Lava.is_walkable());
+        assert!(!TileType::Chasm.is_walkable());
+    }
+
+    #[test]
+    fn test_only_walls_and_shut_doors_block_sight() {
+        assert!(TileType::Wall.blocks_sight());
+        assert!(!TileType::Floor.blocks_sight());
+        assert!(!TileType::StairsDown.blocks_sight());
+        assert!(!TileType::StairsUp.blocks_sight());
     }
 
     #[test]
@@ -43,5 +199,6 @@ mod tests {
         assert_eq!(TileType::Wall.to_char(), '#');
         assert_eq!(TileType::Floor.to_char(), '.');
         assert_eq!(TileType::StairsDown.to_char(), '>');
+        assert_eq!(TileType::StairsUp.to_char(), '<');
     }
 }
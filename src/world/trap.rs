@@ -0,0 +1,120 @@
+use crate::{entity::position::Position, game::config::MAX_DEPTH};
+use rand::{Rng, seq::SliceRandom};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// The kinds of trap set into dungeon floors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrapKind {
+    /// Sharpened stakes at the bottom of a pit
+    SpikePit,
+    /// Shoots a poisoned dart out of the wall
+    PoisonDart,
+    /// Rings a bell that wakes every monster on the floor
+    Alarm,
+    /// Whisks whoever steps on it to a random spot on the floor
+    Teleport,
+    /// Drops whoever steps on it onto the floor below
+    Trapdoor,
+}
+
+impl TrapKind {
+    pub const ALL: [TrapKind; 5] = [
+        TrapKind::SpikePit,
+        TrapKind::PoisonDart,
+        TrapKind::Alarm,
+        TrapKind::Teleport,
+        TrapKind::Trapdoor,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrapKind::SpikePit => "spike pit",
+            TrapKind::PoisonDart => "poison dart trap",
+            TrapKind::Alarm => "alarm trap",
+            TrapKind::Teleport => "teleport trap",
+            TrapKind::Trapdoor => "trapdoor",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            TrapKind::SpikePit => Color::Gray,
+            TrapKind::PoisonDart => Color::Green,
+            TrapKind::Alarm => Color::Yellow,
+            TrapKind::Teleport => Color::LightMagenta,
+            TrapKind::Trapdoor => Color::LightRed,
+        }
+    }
+
+    /// Shallowest depth at which this kind can appear
+    pub fn min_depth(&self) -> u32 {
+        match self {
+            TrapKind::SpikePit | TrapKind::Alarm => 1,
+            TrapKind::PoisonDart | TrapKind::Trapdoor => 2,
+            TrapKind::Teleport => 3,
+        }
+    }
+
+    /// Picks a random kind that is allowed to appear at `depth`
+    ///
+    /// The floor above the boss arena gets no trapdoors, so that the arena
+    /// is only ever entered by its stairs.
+    pub fn random_for_depth<R: Rng>(depth: u32, rng: &mut R) -> TrapKind {
+        let candidates: Vec<TrapKind> = Self::ALL
+            .into_iter()
+            .filter(|kind| kind.min_depth() <= depth)
+            .filter(|&kind| kind != TrapKind::Trapdoor || depth + 1 < MAX_DEPTH)
+            .collect();
+        *candidates
+            .choose(rng)
+            .expect("At least one trap kind must be available at depth 1")
+    }
+}
+
+/// A trap set into the floor; hidden traps look like plain floor until found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trap {
+    pub kind: TrapKind,
+    pub position: Position,
+    pub hidden: bool,
+}
+
+impl Trap {
+    /// A freshly set trap, hidden from the player
+    pub fn new(kind: TrapKind, position: Position) -> Self {
+        Trap {
+            kind,
+            position,
+            hidden: true,
+        }
+    }
+
+    pub fn to_char(&self) -> char {
+        '^'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_new_traps_are_hidden() {
+        let trap = Trap::new(TrapKind::SpikePit, Position { x: 1, y: 2 });
+        assert!(trap.hidden);
+    }
+
+    #[test]
+    fn test_random_kind_respects_depth() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            assert!(TrapKind::random_for_depth(1, &mut rng).min_depth() <= 1);
+            assert_ne!(
+                TrapKind::random_for_depth(MAX_DEPTH - 1, &mut rng),
+                TrapKind::Trapdoor
+            );
+        }
+    }
+}